@@ -87,6 +87,14 @@ impl Generic {
 impl Allocate for Generic {
     fn index(&self) -> usize { self.index() }
     fn peers(&self) -> usize { self.peers() }
+    fn local_peers(&self) -> std::ops::Range<usize> {
+        match self {
+            Generic::Thread(t) => t.local_peers(),
+            Generic::Process(p) => p.local_peers(),
+            Generic::ProcessBinary(pb) => pb.local_peers(),
+            Generic::ZeroCopy(z) => z.local_peers(),
+        }
+    }
     fn allocate<T: Data>(&mut self, identifier: usize) -> (Vec<Box<dyn Push<Message<T>>>>, Box<dyn Pull<Message<T>>>) {
         self.allocate(identifier)
     }
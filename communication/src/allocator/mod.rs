@@ -3,15 +3,18 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::time::Duration;
+use std::ops::Range;
 use std::collections::VecDeque;
 
 pub use self::thread::Thread;
 pub use self::process::Process;
 pub use self::generic::{Generic, GenericBuilder};
+pub use self::sim::Sim;
 
 pub mod thread;
 pub mod process;
 pub mod generic;
+pub mod sim;
 
 pub mod canary;
 pub mod counters;
@@ -20,6 +23,14 @@ pub mod zero_copy;
 
 use crate::{Data, Push, Pull, Message};
 
+/// The largest number of drained elements a channel holds for reuse by its pushers.
+///
+/// Intra-thread and intra-process pullers return each element they are done with to their
+/// channel, which hands it back to a pusher in exchange for a later pushed element, so that its
+/// allocations can be reused. Elements returned while the channel already holds this many are
+/// dropped.
+pub(crate) const RECYCLED_ELEMENTS: usize = 16;
+
 /// A proto-allocator, which implements `Send` and can be completed with `build`.
 ///
 /// This trait exists because some allocators contain elements that do not implement
@@ -42,6 +53,11 @@ pub trait Allocate {
     fn index(&self) -> usize;
     /// The number of workers in the communication group.
     fn peers(&self) -> usize;
+    /// The indices of the workers in the same process as this worker, including itself.
+    ///
+    /// Records sent to these workers do not cross the network. By default all workers are in
+    /// the same process.
+    fn local_peers(&self) -> Range<usize> { 0 .. self.peers() }
     /// Constructs several send endpoints and one receive endpoint.
     fn allocate<T: Data>(&mut self, identifier: usize) -> (Vec<Box<dyn Push<Message<T>>>>, Box<dyn Pull<Message<T>>>);
     /// A shared queue of communication events with channel identifier.
@@ -9,7 +9,7 @@ use std::time::Duration;
 use std::collections::{HashMap, VecDeque};
 
 use crate::allocator::thread::{ThreadBuilder};
-use crate::allocator::{Allocate, AllocateBuilder, Event, Thread};
+use crate::allocator::{Allocate, AllocateBuilder, Event, Thread, RECYCLED_ELEMENTS};
 use crate::{Push, Pull, Message};
 use crate::buzzer::Buzzer;
 
@@ -127,9 +127,10 @@ impl Allocate for Process {
                 let mut pullers = Vec::new();
                 for index in 0 .. self.peers {
                     let (s, r): (Sender<Message<T>>, Receiver<Message<T>>) = channel();
+                    let returns = Arc::new(Mutex::new(VecDeque::new()));
                     // TODO: the buzzer in the pusher may be redundant, because we need to buzz post-counter.
-                    pushers.push((Pusher { target: s }, self.buzzers[index].clone()));
-                    pullers.push(Puller { source: r, current: None });
+                    pushers.push((Pusher { target: s, returns: returns.clone() }, self.buzzers[index].clone()));
+                    pullers.push(Puller { source: r, returns, current: None });
                 }
 
                 let mut to_box = Vec::new();
@@ -191,24 +192,34 @@ impl Allocate for Process {
 }
 
 /// The push half of an intra-process channel.
+///
+/// Each push of an element may return in its place an element that the channel's puller has
+/// finished with, so that its allocations can be reused by the sender. Pushing `None` returns
+/// nothing. The returned elements are shared by all pushers into the same puller.
 struct Pusher<T> {
     target: Sender<T>,
+    returns: Arc<Mutex<VecDeque<T>>>,
 }
 
 impl<T> Clone for Pusher<T> {
     fn clone(&self) -> Self {
         Self {
             target: self.target.clone(),
+            returns: self.returns.clone(),
         }
     }
 }
 
 impl<T> Push<T> for Pusher<T> {
     #[inline] fn push(&mut self, element: &mut Option<T>) {
-        if let Some(element) = element.take() {
+        if let Some(pushed) = element.take() {
             // The remote endpoint could be shut down, and so
             // it is not fundamentally an error to fail to send.
-            let _ = self.target.send(element);
+            let _ = self.target.send(pushed);
+            // take a returned element if one is available, without waiting for the lock.
+            if let Ok(mut returns) = self.returns.try_lock() {
+                *element = returns.pop_front();
+            }
         }
     }
 }
@@ -217,11 +228,20 @@ impl<T> Push<T> for Pusher<T> {
 struct Puller<T> {
     current: Option<T>,
     source: Receiver<T>,
+    returns: Arc<Mutex<VecDeque<T>>>,
 }
 
 impl<T> Pull<T> for Puller<T> {
     #[inline]
     fn pull(&mut self) -> &mut Option<T> {
+        // return the previous element to the pushers, for reuse.
+        if let Some(element) = self.current.take() {
+            if let Ok(mut returns) = self.returns.try_lock() {
+                if returns.len() < RECYCLED_ELEMENTS {
+                    returns.push_back(element);
+                }
+            }
+        }
         self.current = self.source.try_recv().ok();
         &mut self.current
     }
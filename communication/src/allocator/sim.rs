@@ -0,0 +1,137 @@
+//! An allocator which delays the delivery of messages, for testing.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::time::Duration;
+use std::collections::VecDeque;
+
+use crate::allocator::{Allocate, Event};
+use crate::{Data, Push, Pull, Message};
+
+/// Wraps an allocator so that messages in its channels are delivered after random delays.
+///
+/// Each time a channel is pulled from while it has messages, with probability `delay` the channel
+/// withholds them for the moment and asks to be revisited. The random choices are seeded, so a
+/// schedule that exposes a dependence on delivery timing can be reproduced. Messages within each
+/// channel are never reordered, duplicated, or dropped, as progress tracking relies on each of
+/// these guarantees; only the relative timing of channels changes. Channels constructed by
+/// `pipeline` are not delayed.
+///
+/// # Examples
+/// ```
+/// use timely_communication::{Allocate, Message, Push, Pull};
+/// use timely_communication::allocator::{Thread, Sim};
+///
+/// let mut allocator = Sim::new(Thread::new(), 0, 0.5);
+/// let (mut senders, mut receiver) = allocator.allocate::<u64>(0);
+/// for round in 0 .. 10 {
+///     senders[0].send(Message::from_typed(round));
+/// }
+///
+/// // messages arrive in order, though not at each attempt.
+/// let mut received = Vec::new();
+/// let mut attempts = 0;
+/// while received.len() < 10 {
+///     if let Some(message) = receiver.recv() {
+///         received.push(*message);
+///     }
+///     attempts += 1;
+/// }
+/// assert_eq!(received, (0 .. 10).collect::<Vec<_>>());
+/// assert!(attempts > 10);
+/// ```
+pub struct Sim<A: Allocate> {
+    inner: A,
+    generator: Rc<RefCell<Generator>>,
+    delay: f64,
+}
+
+impl<A: Allocate> Sim<A> {
+    /// Wraps `inner`, withholding messages with probability `delay` using choices seeded by `seed`.
+    pub fn new(inner: A, seed: u64, delay: f64) -> Self {
+        assert!((0.0 .. 1.0).contains(&delay), "delay must be in [0, 1)");
+        Sim {
+            inner,
+            generator: Rc::new(RefCell::new(Generator { state: seed })),
+            delay,
+        }
+    }
+}
+
+impl<A: Allocate> Allocate for Sim<A> {
+    fn index(&self) -> usize { self.inner.index() }
+    fn peers(&self) -> usize { self.inner.peers() }
+    fn local_peers(&self) -> std::ops::Range<usize> { self.inner.local_peers() }
+    fn allocate<T: Data>(&mut self, identifier: usize) -> (Vec<Box<dyn Push<Message<T>>>>, Box<dyn Pull<Message<T>>>) {
+        let (pushers, puller) = self.inner.allocate(identifier);
+        let puller = Puller {
+            puller,
+            identifier,
+            events: self.inner.events().clone(),
+            generator: self.generator.clone(),
+            delay: self.delay,
+            pending: VecDeque::new(),
+            current: None,
+        };
+        (pushers, Box::new(puller))
+    }
+    fn events(&self) -> &Rc<RefCell<VecDeque<(usize, Event)>>> {
+        self.inner.events()
+    }
+    fn await_events(&self, duration: Option<Duration>) {
+        self.inner.await_events(duration);
+    }
+    fn receive(&mut self) {
+        self.inner.receive();
+    }
+    fn release(&mut self) {
+        self.inner.release();
+    }
+}
+
+/// The pull half of a delayed channel.
+struct Puller<T> {
+    puller: Box<dyn Pull<Message<T>>>,
+    identifier: usize,
+    events: Rc<RefCell<VecDeque<(usize, Event)>>>,
+    generator: Rc<RefCell<Generator>>,
+    delay: f64,
+    pending: VecDeque<Message<T>>,
+    current: Option<Message<T>>,
+}
+
+impl<T> Pull<Message<T>> for Puller<T> {
+    fn pull(&mut self) -> &mut Option<Message<T>> {
+        while let Some(message) = self.puller.pull().take() {
+            self.pending.push_back(message);
+        }
+        self.current = None;
+        if !self.pending.is_empty() {
+            if self.generator.borrow_mut().next_f64() < self.delay {
+                // withhold the messages, and ensure the channel is revisited.
+                self.events.borrow_mut().push_back((self.identifier, Event::Pushed(0)));
+            }
+            else {
+                self.current = self.pending.pop_front();
+            }
+        }
+        &mut self.current
+    }
+}
+
+/// A small pseudo-random number generator (splitmix64).
+struct Generator {
+    state: u64,
+}
+
+impl Generator {
+    /// A value uniformly distributed in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
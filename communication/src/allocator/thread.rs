@@ -5,7 +5,7 @@ use std::cell::RefCell;
 use std::time::Duration;
 use std::collections::VecDeque;
 
-use crate::allocator::{Allocate, AllocateBuilder, Event};
+use crate::allocator::{Allocate, AllocateBuilder, Event, RECYCLED_ELEMENTS};
 use crate::allocator::counters::Pusher as CountPusher;
 use crate::allocator::counters::Puller as CountPuller;
 use crate::{Push, Pull, Message};
@@ -75,6 +75,29 @@ impl Thread {
 
 
 /// The push half of an intra-thread channel.
+///
+/// Each push of an element may return in its place an element the puller has finished with,
+/// so that its allocations can be reused by the sender. Pushing `None` returns nothing.
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+/// use std::cell::RefCell;
+/// use std::collections::VecDeque;
+/// use timely_communication::{Push, Pull, Message};
+/// use timely_communication::allocator::thread::Thread;
+///
+/// let (mut pusher, mut puller) = Thread::new_from::<Vec<u64>>(0, Rc::new(RefCell::new(VecDeque::new())));
+///
+/// pusher.push(&mut Some(Message::from_typed(vec![0, 1, 2])));
+/// assert!(puller.pull().is_some());
+/// assert!(puller.pull().is_none());
+///
+/// // the first message is returned in exchange for the second.
+/// let mut element = Some(Message::from_typed(vec![3, 4, 5]));
+/// pusher.push(&mut element);
+/// assert_eq!(element.map(|x| x.into_typed()), Some(vec![0, 1, 2]));
+/// ```
 pub struct Pusher<T> {
     target: Rc<RefCell<(VecDeque<T>, VecDeque<T>)>>,
 }
@@ -83,10 +106,10 @@ impl<T> Push<T> for Pusher<T> {
     #[inline]
     fn push(&mut self, element: &mut Option<T>) {
         let mut borrow = self.target.borrow_mut();
-        if let Some(element) = element.take() {
-            borrow.0.push_back(element);
+        if let Some(pushed) = element.take() {
+            borrow.0.push_back(pushed);
+            *element = borrow.1.pop_front();
         }
-        *element = borrow.1.pop_front();
     }
 }
 
@@ -100,12 +123,12 @@ impl<T> Pull<T> for Puller<T> {
     #[inline]
     fn pull(&mut self) -> &mut Option<T> {
         let mut borrow = self.source.borrow_mut();
-        // if let Some(element) = self.current.take() {
-        //     // TODO : Arbitrary constant.
-        //     if borrow.1.len() < 16 {
-        //         borrow.1.push_back(element);
-        //     }
-        // }
+        // return the previous element to the pusher, for reuse.
+        if let Some(element) = self.current.take() {
+            if borrow.1.len() < RECYCLED_ELEMENTS {
+                borrow.1.push_back(element);
+            }
+        }
         self.current = borrow.0.pop_front();
         &mut self.current
     }
@@ -3,7 +3,9 @@
 use std::sync::Arc;
 // use crate::allocator::Process;
 use crate::allocator::process::ProcessBuilder;
-use crate::networking::create_sockets;
+use crate::networking::{Stream, TlsPaths, create_sockets};
+#[cfg(feature = "tls")]
+use crate::networking::{UNIX_PREFIX, SHM_PREFIX};
 use super::tcp::{send_loop, recv_loop};
 use super::allocator::{TcpBuilder, new_vector};
 
@@ -33,6 +35,11 @@ use crate::logging::{CommunicationSetup, CommunicationEvent};
 use logging_core::Logger;
 
 /// Initializes network connections
+///
+/// If every address names a Unix domain socket, as `unix:<path>`, processes connect through those
+/// sockets rather than through TCP. On Linux, if every address names a rendezvous socket for shared
+/// memory, as `shm:<path>`, processes exchange data through ring buffers in shared memory. With the
+/// `lz4` feature, all data sent between processes, including progress updates, are compressed.
 pub fn initialize_networking(
     addresses: Vec<String>,
     my_index: usize,
@@ -41,7 +48,73 @@ pub fn initialize_networking(
     log_sender: Box<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEvent, CommunicationSetup>>+Send+Sync>)
 -> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
 {
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(paths) = crate::networking::shared_memory::shm_paths(&addresses) {
+            let streams = crate::networking::shared_memory::create_shared_memory_streams(paths, my_index, noisy)?;
+            return initialize_networking_from_streams(streams, my_index, threads, log_sender);
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        if let Some(paths) = crate::networking::unix_paths(&addresses) {
+            let sockets = crate::networking::create_unix_sockets(paths, my_index, noisy)?;
+            return initialize_networking_from_streams(sockets, my_index, threads, log_sender);
+        }
+    }
+
     let sockets = create_sockets(addresses, my_index, noisy)?;
+    initialize_networking_from_streams(sockets, my_index, threads, log_sender)
+}
+
+/// Initializes network connections over TCP, secured with TLS.
+///
+/// Each process presents the certificate in `tls`, and accepts only connections whose
+/// certificates are signed by its authority. Addresses must be TCP addresses.
+#[cfg(feature = "tls")]
+pub fn initialize_networking_with_tls(
+    addresses: Vec<String>,
+    my_index: usize,
+    threads: usize,
+    noisy: bool,
+    tls: TlsPaths,
+    log_sender: Box<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEvent, CommunicationSetup>>+Send+Sync>)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+{
+    if addresses.iter().any(|address| address.starts_with(UNIX_PREFIX) || address.starts_with(SHM_PREFIX)) {
+        return Err(::std::io::Error::new(::std::io::ErrorKind::InvalidInput, "TLS connections require TCP addresses"));
+    }
+    let sockets = create_sockets(addresses.clone(), my_index, noisy)?;
+    let streams = crate::networking::tls::secure_sockets(sockets, &addresses, my_index, &tls)?;
+    initialize_networking_from_streams(streams, my_index, threads, log_sender)
+}
+
+/// Reports that TLS requires the `tls` feature.
+#[cfg(not(feature = "tls"))]
+pub fn initialize_networking_with_tls(
+    _addresses: Vec<String>,
+    _my_index: usize,
+    _threads: usize,
+    _noisy: bool,
+    _tls: TlsPaths,
+    _log_sender: Box<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEvent, CommunicationSetup>>+Send+Sync>)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+{
+    Err(::std::io::Error::new(::std::io::ErrorKind::Unsupported, "TLS connections require the `tls` feature"))
+}
+
+/// Initializes send and recv threads from connected streams, compressing their data if the
+/// `lz4` feature is enabled.
+fn initialize_networking_from_streams<S: Stream>(
+    sockets: Vec<Option<S>>,
+    my_index: usize,
+    threads: usize,
+    log_sender: Box<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEvent, CommunicationSetup>>+Send+Sync>)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+{
+    #[cfg(feature = "lz4")]
+    let sockets = sockets.into_iter().map(|socket| socket.map(crate::networking::Compressed::new)).collect::<Vec<_>>();
     initialize_networking_from_sockets(sockets, my_index, threads, log_sender)
 }
 
@@ -52,8 +125,8 @@ pub fn initialize_networking(
 ///
 /// It is important that the `sockets` argument contain sockets for each remote process, in order, and
 /// with position `my_index` set to `None`.
-pub fn initialize_networking_from_sockets(
-    mut sockets: Vec<Option<std::net::TcpStream>>,
+pub fn initialize_networking_from_sockets<S: Stream>(
+    mut sockets: Vec<Option<S>>,
     my_index: usize,
     threads: usize,
     log_sender: Box<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEvent, CommunicationSetup>>+Send+Sync>)
@@ -1,10 +1,9 @@
 //!
 
 use std::io::{Read, Write};
-use std::net::TcpStream;
 use std::sync::mpsc::{Sender, Receiver};
 
-use crate::networking::MessageHeader;
+use crate::networking::{MessageHeader, Stream};
 
 use super::bytes_slab::BytesSlab;
 use super::bytes_exchange::MergeQueue;
@@ -13,14 +12,14 @@ use logging_core::Logger;
 
 use crate::logging::{CommunicationEvent, CommunicationSetup, MessageEvent, StateEvent};
 
-/// Repeatedly reads from a TcpStream and carves out messages.
+/// Repeatedly reads from a stream and carves out messages.
 ///
 /// The intended communication pattern is a sequence of (header, message)^* for valid
 /// messages, followed by a header for a zero length message indicating the end of stream.
 /// If the stream ends without being shut down, the receive thread panics in an attempt to
 /// take down the computation and cause the failures to cascade.
-pub fn recv_loop(
-    mut reader: TcpStream,
+pub fn recv_loop<S: Read>(
+    mut reader: S,
     targets: Vec<Receiver<MergeQueue>>,
     worker_offset: usize,
     process: usize,
@@ -107,13 +106,13 @@ pub fn recv_loop(
     logger.as_mut().map(|l| l.log(StateEvent { send: false, process, remote, start: false, }));
 }
 
-/// Repeatedly sends messages into a TcpStream.
+/// Repeatedly sends messages into a stream.
 ///
 /// The intended communication pattern is a sequence of (header, message)^* for valid
 /// messages, followed by a header for a zero length message indicating the end of stream.
-pub fn send_loop(
+pub fn send_loop<S: Stream>(
     // TODO: Maybe we don't need BufWriter with consolidation in writes.
-    writer: TcpStream,
+    writer: S,
     sources: Vec<Sender<MergeQueue>>,
     process: usize,
     remote: usize,
@@ -184,7 +183,7 @@ pub fn send_loop(
     };
     header.write_to(&mut writer).expect("Failed to write header!");
     writer.flush().expect("Failed to flush writer.");
-    writer.get_mut().shutdown(::std::net::Shutdown::Write).expect("Write shutdown failed");
+    writer.get_mut().shutdown_write().expect("Write shutdown failed");
     logger.as_mut().map(|logger| logger.log(MessageEvent { is_send: true, header }));
 
     // Log the receive thread's start.
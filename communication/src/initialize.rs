@@ -11,7 +11,8 @@ use std::any::Any;
 
 use crate::allocator::thread::ThreadBuilder;
 use crate::allocator::{AllocateBuilder, Process, Generic, GenericBuilder};
-use crate::allocator::zero_copy::initialize::initialize_networking;
+use crate::allocator::zero_copy::initialize::{initialize_networking, initialize_networking_with_tls};
+use crate::networking::TlsPaths;
 
 use crate::logging::{CommunicationSetup, CommunicationEvent};
 use logging_core::Logger;
@@ -24,15 +25,123 @@ pub enum Configuration {
     /// Use one process with an indicated number of threads.
     Process(usize),
     /// Expect multiple processes.
+    ///
+    /// Each process connects to every other process over TCP, using the address at its index
+    /// in `addresses`, and then exchanges length-prefixed binary frames with each of them on a
+    /// dedicated send thread and receive thread. Every process must be started with the same
+    /// list of addresses and its own index in `process`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// // process 0 of two, each with two worker threads; process 1 would use `process: 1`.
+    /// let config = timely_communication::Configuration::Cluster {
+    ///     threads: 2,
+    ///     process: 0,
+    ///     addresses: vec!["host0:2101".to_string(), "host1:2101".to_string()],
+    ///     report: false,
+    ///     tls: None,
+    ///     log_fn: Box::new(|_| None),
+    /// };
+    ///
+    /// let guards = timely_communication::initialize(config, |allocator| {
+    ///     use timely_communication::Allocate;
+    ///     // indices range over the workers of all processes.
+    ///     assert_eq!(allocator.peers(), 4);
+    /// });
+    /// ```
+    ///
+    /// Processes on the same host can instead connect through Unix domain sockets, by naming
+    /// the path of each process's socket as `unix:<path>`.
+    ///
+    /// ```
+    /// use timely_communication::{Allocate, Configuration, Message};
+    ///
+    /// let directory = ::std::env::temp_dir();
+    /// let addresses = (0..2).map(|index| {
+    ///     let path = directory.join(format!("timely-{}-{}.sock", ::std::process::id(), index));
+    ///     format!("unix:{}", path.display())
+    /// }).collect::<Vec<_>>();
+    ///
+    /// // start each of two processes, here as threads of this process.
+    /// let processes = (0..2).map(|process| {
+    ///     let addresses = addresses.clone();
+    ///     ::std::thread::spawn(move || {
+    ///         let config = Configuration::Cluster {
+    ///             threads: 1,
+    ///             process,
+    ///             addresses,
+    ///             report: false,
+    ///             tls: None,
+    ///             log_fn: Box::new(|_| None),
+    ///         };
+    ///         let guards = timely_communication::initialize(config, |mut allocator| {
+    ///             // send this worker's index to the other worker, and return the index received.
+    ///             let (mut senders, mut receiver) = allocator.allocate(0);
+    ///             senders[1 - allocator.index()].send(Message::from_typed(allocator.index()));
+    ///             loop {
+    ///                 allocator.receive();
+    ///                 let received = receiver.recv().map(|message| *message);
+    ///                 allocator.release();
+    ///                 if let Some(index) = received { break index; }
+    ///             }
+    ///         }).unwrap();
+    ///         guards.join().into_iter().map(|result| result.unwrap()).collect::<Vec<_>>()
+    ///     })
+    /// }).collect::<Vec<_>>();
+    ///
+    /// let received = processes.into_iter().map(|process| process.join().unwrap()).collect::<Vec<_>>();
+    /// assert_eq!(received, vec![vec![1], vec![0]]);
+    /// ```
+    ///
+    /// On Linux, processes on the same host can exchange data through ring buffers in shared
+    /// memory, by naming the path of each process's rendezvous socket as `shm:<path>`. The
+    /// processes connect as with `unix:<path>` addresses, and then use the sockets only to set up
+    /// the shared memory.
+    ///
+    /// ```
+    /// use timely_communication::{Allocate, Configuration, Message};
+    ///
+    /// let directory = ::std::env::temp_dir();
+    /// let addresses = (0..2).map(|index| {
+    ///     let path = directory.join(format!("timely-shm-{}-{}.sock", ::std::process::id(), index));
+    ///     format!("shm:{}", path.display())
+    /// }).collect::<Vec<_>>();
+    ///
+    /// let processes = (0..2).map(|process| {
+    ///     let addresses = addresses.clone();
+    ///     ::std::thread::spawn(move || {
+    ///         let config = Configuration::Cluster {
+    ///             threads: 1, process, addresses, report: false, tls: None, log_fn: Box::new(|_| None),
+    ///         };
+    ///         let guards = timely_communication::initialize(config, |mut allocator| {
+    ///             // send more data than fits in a ring buffer at once.
+    ///             let (mut senders, mut receiver) = allocator.allocate(0);
+    ///             senders[1 - allocator.index()].send(Message::from_typed(vec![allocator.index(); 1 << 20]));
+    ///             loop {
+    ///                 allocator.receive();
+    ///                 let received = receiver.recv().map(|message| message.to_vec());
+    ///                 allocator.release();
+    ///                 if let Some(data) = received { break data; }
+    ///             }
+    ///         }).unwrap();
+    ///         guards.join().into_iter().map(|result| result.unwrap()).collect::<Vec<_>>()
+    ///     })
+    /// }).collect::<Vec<_>>();
+    ///
+    /// let received = processes.into_iter().map(|process| process.join().unwrap()).collect::<Vec<_>>();
+    /// assert_eq!(received, vec![vec![vec![1; 1 << 20]], vec![vec![0; 1 << 20]]]);
+    /// ```
     Cluster {
         /// Number of per-process worker threads
         threads: usize,
         /// Identity of this process
         process: usize,
-        /// Addresses of all processes
+        /// Addresses of all processes, either all TCP hosts, all `unix:<path>` sockets, or all `shm:<path>` sockets
         addresses: Vec<String>,
         /// Verbosely report connection process
         report: bool,
+        /// Paths of the certificates with which to secure TCP connections with TLS, if any
+        tls: Option<TlsPaths>,
         /// Closure to create a new logger for a communication thread
         log_fn: Box<dyn Fn(CommunicationSetup) -> Option<Logger<CommunicationEvent, CommunicationSetup>> + Send + Sync>,
     }
@@ -50,57 +159,98 @@ impl Configuration {
         opts.optopt("n", "processes", "number of processes", "NUM");
         opts.optopt("h", "hostfile", "text file whose lines are process addresses", "FILE");
         opts.optflag("r", "report", "reports connection progress");
+        opts.optopt("", "tls-cert", "PEM file of this process's TLS certificate chain", "FILE");
+        opts.optopt("", "tls-key", "PEM file of this process's TLS private key", "FILE");
+        opts.optopt("", "tls-ca", "PEM file of the authority signing all processes' certificates", "FILE");
 
         opts
     }
 
     /// Constructs a new configuration by parsing supplied text arguments.
     ///
-    /// Most commonly, this uses `std::env::Args()` as the supplied iterator.
+    /// Most commonly, this uses `std::env::Args()` as the supplied iterator. Malformed numbers,
+    /// a process identity that is not less than the number of processes, and a host file with
+    /// too few addresses are reported as errors.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely_communication::Configuration;
+    ///
+    /// let args = |text: &str| text.split_whitespace().map(|x| x.to_string()).collect::<Vec<_>>().into_iter();
+    ///
+    /// match Configuration::from_args(args("program -w 4")) {
+    ///     Ok(Configuration::Process(4)) => { },
+    ///     _ => panic!("expected four threads in one process"),
+    /// }
+    ///
+    /// match Configuration::from_args(args("program -w 2 -n 3 -p 1")) {
+    ///     Ok(Configuration::Cluster { threads, process, addresses, .. }) => {
+    ///         assert_eq!((threads, process), (2, 1));
+    ///         assert_eq!(addresses, vec!["localhost:2101", "localhost:2102", "localhost:2103"]);
+    ///     },
+    ///     _ => panic!("expected a cluster of three processes"),
+    /// }
+    ///
+    /// assert!(Configuration::from_args(args("program -w four")).is_err());
+    /// assert!(Configuration::from_args(args("program -n 2 -p 2")).is_err());
+    /// ```
     pub fn from_args<I: Iterator<Item=String>>(args: I) -> Result<Configuration,String> {
         let opts = Configuration::options();
 
-        opts.parse(args)
-            .map_err(|e| format!("{:?}", e))
-            .map(|matches| {
+        let matches = opts.parse(args).map_err(|e| format!("{:?}", e))?;
 
-            // let mut config = Configuration::new(1, 0, Vec::new());
-            let threads = matches.opt_str("w").map(|x| x.parse().unwrap_or(1)).unwrap_or(1);
-            let process = matches.opt_str("p").map(|x| x.parse().unwrap_or(0)).unwrap_or(0);
-            let processes = matches.opt_str("n").map(|x| x.parse().unwrap_or(1)).unwrap_or(1);
-            let report = matches.opt_present("report");
+        let parse = |name: &str, default: usize| -> Result<usize, String> {
+            match matches.opt_str(name) {
+                Some(text) => text.parse().map_err(|_| format!("-{}: expected a number, found {:?}", name, text)),
+                None => Ok(default),
+            }
+        };
 
-            assert!(process < processes);
+        let threads = parse("w", 1)?;
+        let process = parse("p", 0)?;
+        let processes = parse("n", 1)?;
+        let report = matches.opt_present("report");
+        let tls = match (matches.opt_str("tls-cert"), matches.opt_str("tls-key"), matches.opt_str("tls-ca")) {
+            (Some(certificate), Some(key), Some(authority)) => {
+                Some(TlsPaths { certificate: certificate.into(), key: key.into(), authority: authority.into() })
+            },
+            (None, None, None) => None,
+            _ => return Err("--tls-cert, --tls-key, and --tls-ca must be supplied together".to_string()),
+        };
 
-            if processes > 1 {
-                let mut addresses = Vec::new();
-                if let Some(hosts) = matches.opt_str("h") {
-                    let reader = ::std::io::BufReader::new(::std::fs::File::open(hosts.clone()).unwrap());
-                    for x in reader.lines().take(processes) {
-                        addresses.push(x.unwrap());
-                    }
-                    if addresses.len() < processes {
-                        panic!("could only read {} addresses from {}, but -n: {}", addresses.len(), hosts, processes);
-                    }
+        if process >= processes {
+            return Err(format!("-p: process {} is not less than -n: {}", process, processes));
+        }
+
+        if processes > 1 {
+            let mut addresses = Vec::new();
+            if let Some(hosts) = matches.opt_str("h") {
+                let file = ::std::fs::File::open(&hosts).map_err(|e| format!("-h: {}: {}", hosts, e))?;
+                let reader = ::std::io::BufReader::new(file);
+                for x in reader.lines().take(processes) {
+                    addresses.push(x.map_err(|e| format!("-h: {}: {}", hosts, e))?);
                 }
-                else {
-                    for index in 0..processes {
-                        addresses.push(format!("localhost:{}", 2101 + index));
-                    }
+                if addresses.len() < processes {
+                    return Err(format!("could only read {} addresses from {}, but -n: {}", addresses.len(), hosts, processes));
                 }
-
-                assert!(processes == addresses.len());
-                Configuration::Cluster {
-                    threads,
-                    process,
-                    addresses,
-                    report,
-                    log_fn: Box::new( | _ | None),
+            }
+            else {
+                for index in 0..processes {
+                    addresses.push(format!("localhost:{}", 2101 + index));
                 }
             }
-            else if threads > 1 { Configuration::Process(threads) }
-            else { Configuration::Thread }
-        })
+
+            Ok(Configuration::Cluster {
+                threads,
+                process,
+                addresses,
+                report,
+                tls,
+                log_fn: Box::new( | _ | None),
+            })
+        }
+        else if threads > 1 { Ok(Configuration::Process(threads)) }
+        else { Ok(Configuration::Thread) }
     }
 
     /// Attempts to assemble the described communication infrastructure.
@@ -112,8 +262,12 @@ impl Configuration {
             Configuration::Process(threads) => {
                 Ok((Process::new_vector(threads).into_iter().map(|x| GenericBuilder::Process(x)).collect(), Box::new(())))
             },
-            Configuration::Cluster { threads, process, addresses, report, log_fn } => {
-                match initialize_networking(addresses, process, threads, report, log_fn) {
+            Configuration::Cluster { threads, process, addresses, report, tls, log_fn } => {
+                let networking = match tls {
+                    Some(tls) => initialize_networking_with_tls(addresses, process, threads, report, tls, log_fn),
+                    None => initialize_networking(addresses, process, threads, report, log_fn),
+                };
+                match networking {
                     Ok((stuff, guard)) => {
                         Ok((stuff.into_iter().map(|x| GenericBuilder::ZeroCopy(x)).collect(), Box::new(guard)))
                     },
@@ -81,6 +81,8 @@ extern crate getopts;
 extern crate bincode;
 #[cfg(feature = "bincode")]
 extern crate serde;
+#[cfg(feature = "lz4")]
+extern crate lz4_flex;
 
 extern crate abomonation;
 #[macro_use] extern crate abomonation_derive;
@@ -108,12 +110,20 @@ pub use initialize::{initialize, initialize_from, Configuration, WorkerGuards};
 pub use message::Message;
 
 /// A composite trait for types that may be used with channels.
+///
+/// By default data are serialized with `Abomonation`, which encodes records by copying their
+/// memory directly into the send buffers of network channels, and decodes received records in
+/// place, without copying or allocation.
 #[cfg(not(feature = "bincode"))]
 pub trait Data : Send+Sync+Any+Abomonation+'static { }
 #[cfg(not(feature = "bincode"))]
 impl<T: Send+Sync+Any+Abomonation+'static> Data for T { }
 
 /// A composite trait for types that may be used with channels.
+///
+/// With the `bincode` feature data are serialized with `serde` and `bincode`, so that any type
+/// implementing `Serialize` and `Deserialize` can be exchanged between processes. Received
+/// records are deserialized into owned instances.
 #[cfg(feature = "bincode")]
 pub trait Data : Send+Sync+Any+Serialize+for<'a>Deserialize<'a>+'static { }
 #[cfg(feature = "bincode")]
@@ -125,6 +135,9 @@ impl<T: Send+Sync+Any+Serialize+for<'a>Deserialize<'a>+'static> Data for T { }
 /// which provides the opportunity for zero-copy operation. In the call
 /// to `push(element)` the implementor can *swap* some other value to
 /// replace `element`, effectively returning the value to the caller.
+/// Intra-thread and intra-process channels use this to return, in exchange
+/// for each pushed element, an element their puller has finished with, so
+/// that the caller can reuse its allocations. Pushing `None` returns nothing.
 ///
 /// Conventionally, a sequence of calls to `push()` should conclude with
 /// a call of `push(&mut None)` or `done()` to signal to implementors that
@@ -43,6 +43,11 @@ impl<'a, T: Clone+'a> RefOrMut<'a, T> {
 }
 
 /// A wrapped message which may be either typed or binary data.
+///
+/// Messages received from other processes remain in their serialized form, and are only decoded
+/// in place (without copying) when they are read. A binary message that is sent on to another
+/// process is written out from its bytes, without re-encoding. Mutable access, through `as_mut`
+/// or `into_typed`, is what forces a binary message to be copied into an owned typed instance.
 pub struct Message<T> {
     payload: MessageContents<T>,
 }
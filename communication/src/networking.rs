@@ -1,8 +1,11 @@
 //! Networking code for sending and receiving fixed size `Vec<u8>` between machines.
 
 use std::io;
-use std::io::{Read, Result};
+use std::io::{Read, Write, Result};
 use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
 use std::sync::Arc;
 use std::thread;
 use std::thread::sleep;
@@ -10,6 +13,11 @@ use std::time::Duration;
 
 use abomonation::{encode, decode};
 
+#[cfg(target_os = "linux")]
+pub mod shared_memory;
+#[cfg(feature = "tls")]
+pub mod tls;
+
 // This constant is sent along immediately after establishing a TCP stream, so
 // that it is easy to sniff out Timely traffic when it is multiplexed with
 // other traffic on the same port.
@@ -59,6 +67,161 @@ impl MessageHeader {
     }
 }
 
+/// A bidirectional byte stream connecting this process with one other process.
+///
+/// The send and receive threads of the cluster allocator communicate over any `Stream`.
+pub trait Stream: Read+Write+Sized+Send+'static {
+    /// Creates an independently owned handle to the same stream.
+    fn try_clone(&self) -> Result<Self>;
+    /// Moves the stream into or out of non-blocking mode.
+    fn set_nonblocking(&self, nonblocking: bool) -> Result<()>;
+    /// Indicates to the remote process that no further data will be written.
+    fn shutdown_write(&mut self) -> Result<()>;
+}
+
+impl Stream for TcpStream {
+    fn try_clone(&self) -> Result<Self> { TcpStream::try_clone(self) }
+    fn set_nonblocking(&self, nonblocking: bool) -> Result<()> { TcpStream::set_nonblocking(self, nonblocking) }
+    fn shutdown_write(&mut self) -> Result<()> { self.shutdown(::std::net::Shutdown::Write) }
+}
+
+#[cfg(unix)]
+impl Stream for UnixStream {
+    fn try_clone(&self) -> Result<Self> { UnixStream::try_clone(self) }
+    fn set_nonblocking(&self, nonblocking: bool) -> Result<()> { UnixStream::set_nonblocking(self, nonblocking) }
+    fn shutdown_write(&mut self) -> Result<()> { self.shutdown(::std::net::Shutdown::Write) }
+}
+
+/// A `Stream` whose data are compressed with LZ4.
+///
+/// Each handle compresses the data it writes and decompresses the data it reads, so both ends
+/// of a connection must be wrapped. Data written are sent on each `flush`.
+///
+/// # Examples
+/// ```
+/// use std::io::{Read, Write};
+/// use std::os::unix::net::UnixStream;
+/// use timely_communication::networking::{Stream, Compressed};
+///
+/// let (sender, receiver) = UnixStream::pair().unwrap();
+/// let mut sender = Compressed::new(sender);
+/// let mut receiver = Compressed::new(receiver);
+///
+/// sender.write_all(&[7u8; 1 << 16]).unwrap();
+/// sender.shutdown_write().unwrap();
+///
+/// let mut received = Vec::new();
+/// receiver.read_to_end(&mut received).unwrap();
+/// assert_eq!(received, vec![7u8; 1 << 16]);
+/// ```
+#[cfg(feature = "lz4")]
+pub struct Compressed<S: Stream> {
+    stream: S,
+    encoder: Option<lz4_flex::frame::FrameEncoder<S>>,
+    decoder: Option<lz4_flex::frame::FrameDecoder<S>>,
+}
+
+#[cfg(feature = "lz4")]
+impl<S: Stream> Compressed<S> {
+    /// Wraps a stream so that its data are compressed.
+    pub fn new(stream: S) -> Self {
+        Compressed { stream, encoder: None, decoder: None }
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl<S: Stream> Read for Compressed<S> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.decoder.is_none() {
+            self.decoder = Some(lz4_flex::frame::FrameDecoder::new(self.stream.try_clone()?));
+        }
+        let decoder = self.decoder.as_mut().unwrap();
+        let read = decoder.read(buf)?;
+        if read == 0 && !buf.is_empty() {
+            // the writer's one frame has ended, and so must the stream; read through to its end so
+            // that nothing sent after the frame, such as a TLS closure, is left unread.
+            let mut rest = Vec::new();
+            decoder.get_mut().read_to_end(&mut rest)?;
+            if !rest.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "data after the end of a compressed stream"));
+            }
+        }
+        Ok(read)
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl<S: Stream> Write for Compressed<S> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.encoder.is_none() {
+            self.encoder = Some(lz4_flex::frame::FrameEncoder::new(self.stream.try_clone()?));
+        }
+        self.encoder.as_mut().unwrap().write(buf)
+    }
+    fn flush(&mut self) -> Result<()> {
+        if let Some(encoder) = self.encoder.as_mut() {
+            encoder.flush()?;
+        }
+        self.stream.flush()
+    }
+}
+
+#[cfg(feature = "lz4")]
+impl<S: Stream> Stream for Compressed<S> {
+    fn try_clone(&self) -> Result<Self> { Ok(Compressed::new(self.stream.try_clone()?)) }
+    fn set_nonblocking(&self, nonblocking: bool) -> Result<()> { self.stream.set_nonblocking(nonblocking) }
+    fn shutdown_write(&mut self) -> Result<()> {
+        if let Some(encoder) = self.encoder.as_mut() {
+            encoder.try_finish()?;
+        }
+        self.stream.shutdown_write()
+    }
+}
+
+/// Paths of the PEM files with which processes secure their connections with TLS.
+///
+/// Connections are encrypted, and each process accepts only connections from processes whose
+/// certificates are signed by `authority`. Securing connections requires the `tls` feature.
+#[derive(Clone, Debug)]
+pub struct TlsPaths {
+    /// The certificate chain of this process.
+    pub certificate: PathBuf,
+    /// The private key of this process's certificate.
+    pub key: PathBuf,
+    /// The certificates of the authorities that sign the certificates of all processes.
+    pub authority: PathBuf,
+}
+
+/// The prefix of addresses that name Unix domain sockets rather than TCP hosts.
+pub const UNIX_PREFIX: &str = "unix:";
+
+/// The prefix of addresses that name rendezvous sockets for shared memory connections.
+pub const SHM_PREFIX: &str = "shm:";
+
+/// Returns the socket paths of `addresses`, if they all name Unix domain sockets.
+pub fn unix_paths(addresses: &[String]) -> Option<Vec<String>> {
+    addresses.iter().map(|address| address.strip_prefix(UNIX_PREFIX).map(|path| path.to_string())).collect()
+}
+
+/// Identifies this process to the process at the other end of a new stream.
+fn send_handshake<W: Write>(stream: &mut W, my_index: usize) -> Result<()> {
+    unsafe { encode(&HANDSHAKE_MAGIC, stream) }?;
+    unsafe { encode(&(my_index as u64), stream) }
+}
+
+/// Reads the identity of the process at the other end of a new stream.
+fn recv_handshake<R: Read>(stream: &mut R) -> Result<usize> {
+    let mut buffer = [0u8;16];
+    stream.read_exact(&mut buffer)?;
+    let (magic, buffer) = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode magic");
+    if magic != &HANDSHAKE_MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+            "received incorrect timely handshake"));
+    }
+    let identifier = *unsafe { decode::<u64>(buffer) }.expect("failed to decode worker index").0 as usize;
+    Ok(identifier)
+}
+
 /// Creates socket connections from a list of host addresses.
 ///
 /// The item at index i in the resulting vec, is a Some(TcpSocket) to process i, except
@@ -89,8 +252,7 @@ pub fn start_connections(addresses: Arc<Vec<String>>, my_index: usize, noisy: bo
             match TcpStream::connect(address) {
                 Ok(mut stream) => {
                     stream.set_nodelay(true).expect("set_nodelay call failed");
-                    unsafe { encode(&HANDSHAKE_MAGIC, &mut stream) }.expect("failed to encode/send handshake magic");
-                    unsafe { encode(&(my_index as u64), &mut stream) }.expect("failed to encode/send worker index");
+                    send_handshake(&mut stream, my_index).expect("failed to encode/send handshake");
                     if noisy { println!("worker {}:\tconnection to worker {}", my_index, index); }
                     break Some(stream);
                 },
@@ -113,17 +275,52 @@ pub fn await_connections(addresses: Arc<Vec<String>>, my_index: usize, noisy: bo
     for _ in (my_index + 1) .. addresses.len() {
         let mut stream = listener.accept()?.0;
         stream.set_nodelay(true).expect("set_nodelay call failed");
-        let mut buffer = [0u8;16];
-        stream.read_exact(&mut buffer)?;
-        let (magic, mut buffer) = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode magic");
-        if magic != &HANDSHAKE_MAGIC {
-            return Err(io::Error::new(io::ErrorKind::InvalidData,
-                "received incorrect timely handshake"));
-        }
-        let identifier = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode worker index").0.clone() as usize;
+        let identifier = recv_handshake(&mut stream)?;
         results[identifier - my_index - 1] = Some(stream);
         if noisy { println!("worker {}:\tconnection from worker {}", my_index, identifier); }
     }
 
     Ok(results)
 }
+
+/// Creates Unix domain socket connections from a list of socket paths.
+///
+/// This is the analogue of `create_sockets` for processes on the same host, and avoids the
+/// TCP stack. Each process listens at the path at its own index, replacing any file there.
+#[cfg(unix)]
+pub fn create_unix_sockets(paths: Vec<String>, my_index: usize, noisy: bool) -> Result<Vec<Option<UnixStream>>> {
+
+    // bind before connecting, so that no process waits on another's listener.
+    let _ = ::std::fs::remove_file(&paths[my_index]);
+    let listener = UnixListener::bind(&paths[my_index])?;
+
+    let mut results = paths.iter().take(my_index).enumerate().map(|(index, path)| {
+        loop {
+            match UnixStream::connect(path) {
+                Ok(mut stream) => {
+                    send_handshake(&mut stream, my_index)?;
+                    if noisy { println!("worker {}:\tconnection to worker {}", my_index, index); }
+                    break Ok(Some(stream));
+                },
+                Err(error) => {
+                    println!("worker {}:\terror connecting to worker {}: {}; retrying", my_index, index, error);
+                    sleep(Duration::from_millis(100));
+                },
+            }
+        }
+    }).collect::<Result<Vec<_>>>()?;
+
+    results.push(None);
+    results.extend((my_index + 1 .. paths.len()).map(|_| None));
+    for _ in (my_index + 1) .. paths.len() {
+        let mut stream = listener.accept()?.0;
+        let identifier = recv_handshake(&mut stream)?;
+        results[identifier] = Some(stream);
+        if noisy { println!("worker {}:\tconnection from worker {}", my_index, identifier); }
+    }
+
+    let _ = ::std::fs::remove_file(&paths[my_index]);
+    if noisy { println!("worker {}:\tinitialization complete", my_index) }
+
+    Ok(results)
+}
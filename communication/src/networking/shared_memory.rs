@@ -0,0 +1,344 @@
+//! A `Stream` between processes on the same host, through ring buffers in shared memory.
+//!
+//! Each connection maps one file holding two single-producer single-consumer ring buffers, one
+//! for each direction. Bytes are copied into and out of the mapping without system calls; a
+//! reader or writer that must wait for the other end sleeps on a futex in the mapping, and is
+//! woken only if it announced that it is waiting.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write, Result};
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use super::{Stream, SHM_PREFIX};
+
+/// The number of data bytes in each direction's ring buffer; a power of two.
+pub const RING_BYTES: usize = 1 << 22;
+
+// bytes reserved for each ring's counters, which sit on separate cache lines.
+const HEADER_BYTES: usize = 256;
+
+// how long a waiting thread sleeps before checking that the other process is still connected.
+const LIVENESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The shared counters of one ring buffer, at the start of its region of the mapping.
+///
+/// `head` and `tail` count the bytes written and read, modulo 2^32. Each `signal` word changes
+/// whenever a waiting thread should look again, and is the word that thread sleeps on.
+#[repr(C)]
+struct Header {
+    head: AtomicU32,
+    data_signal: AtomicU32,
+    reader_waiting: AtomicU32,
+    closed: AtomicU32,
+    _pad0: [u8; 48],
+    tail: AtomicU32,
+    space_signal: AtomicU32,
+    writer_waiting: AtomicU32,
+    reader_gone: AtomicU32,
+    _pad1: [u8; 48],
+}
+
+/// A mapping of the rings of one connection, unmapped when its last handle is dropped.
+struct Mapping {
+    base: *mut u8,
+    // the ring written to and the ring read from, by this process.
+    send: usize,
+    recv: usize,
+    // the rendezvous socket, used only to notice that the other process has exited.
+    socket: UnixStream,
+}
+
+unsafe impl Send for Mapping { }
+unsafe impl Sync for Mapping { }
+
+impl Mapping {
+
+    fn map(file: &File, send: usize, socket: UnixStream) -> Result<Self> {
+        let base = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                mapping_bytes(),
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if base == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mapping { base: base as *mut u8, send, recv: 1 - send, socket })
+    }
+
+    fn header(&self, ring: usize) -> &Header {
+        unsafe { &*(self.base.add(ring * (HEADER_BYTES + RING_BYTES)) as *const Header) }
+    }
+
+    fn data(&self, ring: usize) -> *mut u8 {
+        unsafe { self.base.add(ring * (HEADER_BYTES + RING_BYTES) + HEADER_BYTES) }
+    }
+
+    /// True if the other process has closed its end of the rendezvous socket.
+    fn peer_exited(&self) -> bool {
+        match (&self.socket).read(&mut [0u8]) {
+            Ok(0) => true,
+            Ok(_) => false,
+            Err(error) => error.kind() != io::ErrorKind::WouldBlock,
+        }
+    }
+
+    /// Sleeps until `signal` changes from `expected`, for at most `LIVENESS_INTERVAL`.
+    fn wait(signal: &AtomicU32, expected: u32) {
+        let timeout = libc::timespec {
+            tv_sec: LIVENESS_INTERVAL.as_secs() as libc::time_t,
+            tv_nsec: LIVENESS_INTERVAL.subsec_nanos() as libc::c_long,
+        };
+        unsafe {
+            let timeout = &timeout as *const libc::timespec;
+            libc::syscall(libc::SYS_futex, signal as *const AtomicU32, libc::FUTEX_WAIT, expected, timeout);
+        }
+    }
+
+    /// Changes `signal`, and wakes the thread sleeping on it.
+    fn wake(signal: &AtomicU32) {
+        signal.fetch_add(1, Ordering::SeqCst);
+        unsafe {
+            libc::syscall(libc::SYS_futex, signal as *const AtomicU32, libc::FUTEX_WAKE, i32::MAX);
+        }
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        let send = self.header(self.send);
+        send.closed.store(1, Ordering::SeqCst);
+        Mapping::wake(&send.data_signal);
+        let recv = self.header(self.recv);
+        recv.reader_gone.store(1, Ordering::SeqCst);
+        Mapping::wake(&recv.space_signal);
+        unsafe { libc::munmap(self.base as *mut libc::c_void, mapping_bytes()); }
+    }
+}
+
+fn mapping_bytes() -> usize { 2 * (HEADER_BYTES + RING_BYTES) }
+
+/// One end of a connection through shared memory.
+///
+/// Each handle may read and write, but as with a socket at most one handle should read and at
+/// most one should write at a time.
+pub struct SharedMemory {
+    mapping: Arc<Mapping>,
+}
+
+impl SharedMemory {
+
+    /// Creates both ends of a connection within this process.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::io::{Read, Write};
+    /// use timely_communication::networking::Stream;
+    /// use timely_communication::networking::shared_memory::SharedMemory;
+    ///
+    /// let (mut sender, mut receiver) = SharedMemory::pair().unwrap();
+    ///
+    /// let writer = ::std::thread::spawn(move || {
+    ///     // more than fits in the ring at once.
+    ///     for _ in 0 .. 64 { sender.write_all(&[7u8; 1 << 20]).unwrap(); }
+    ///     sender.shutdown_write().unwrap();
+    /// });
+    ///
+    /// let mut received = Vec::new();
+    /// receiver.read_to_end(&mut received).unwrap();
+    /// writer.join().unwrap();
+    /// assert_eq!(received.len(), 64 << 20);
+    /// assert!(received.iter().all(|&byte| byte == 7));
+    /// ```
+    pub fn pair() -> Result<(Self, Self)> {
+        let (socket0, socket1) = UnixStream::pair()?;
+        let (file, path) = create_file(&format!("timely-{}-pair", ::std::process::id()))?;
+        let _ = ::std::fs::remove_file(&path);
+        Ok((SharedMemory::new(Mapping::map(&file, 0, socket0)?)?, SharedMemory::new(Mapping::map(&file, 1, socket1)?)?))
+    }
+
+    // the rendezvous socket is only polled from here on.
+    fn new(mapping: Mapping) -> Result<Self> {
+        mapping.socket.set_nonblocking(true)?;
+        Ok(SharedMemory { mapping: Arc::new(mapping) })
+    }
+}
+
+impl Read for SharedMemory {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() { return Ok(0); }
+        let ring = self.mapping.header(self.mapping.recv);
+        loop {
+            let tail = ring.tail.load(Ordering::Relaxed);
+            let head = ring.head.load(Ordering::Acquire);
+            if head != tail {
+                let length = ::std::cmp::min(head.wrapping_sub(tail) as usize, buf.len());
+                let offset = tail as usize % RING_BYTES;
+                let first = ::std::cmp::min(length, RING_BYTES - offset);
+                unsafe {
+                    let data = self.mapping.data(self.mapping.recv);
+                    ptr::copy_nonoverlapping(data.add(offset), buf.as_mut_ptr(), first);
+                    ptr::copy_nonoverlapping(data, buf.as_mut_ptr().add(first), length - first);
+                }
+                ring.tail.store(tail.wrapping_add(length as u32), Ordering::SeqCst);
+                if ring.writer_waiting.load(Ordering::SeqCst) != 0 {
+                    Mapping::wake(&ring.space_signal);
+                }
+                return Ok(length);
+            }
+            // the writer closes the ring only after its last bytes, so look again once closed.
+            if ring.closed.load(Ordering::SeqCst) != 0 {
+                if ring.head.load(Ordering::SeqCst) == tail { return Ok(0); }
+                continue;
+            }
+            if self.mapping.peer_exited() {
+                ring.closed.store(1, Ordering::SeqCst);
+                continue;
+            }
+            let signal = ring.data_signal.load(Ordering::SeqCst);
+            ring.reader_waiting.store(1, Ordering::SeqCst);
+            if ring.head.load(Ordering::SeqCst) == tail && ring.closed.load(Ordering::SeqCst) == 0 {
+                Mapping::wait(&ring.data_signal, signal);
+            }
+            ring.reader_waiting.store(0, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Write for SharedMemory {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if buf.is_empty() { return Ok(0); }
+        let ring = self.mapping.header(self.mapping.send);
+        loop {
+            if ring.reader_gone.load(Ordering::SeqCst) != 0 {
+                return Err(io::Error::new(io::ErrorKind::BrokenPipe, "shared memory reader has closed"));
+            }
+            let head = ring.head.load(Ordering::Relaxed);
+            let tail = ring.tail.load(Ordering::Acquire);
+            let free = RING_BYTES - head.wrapping_sub(tail) as usize;
+            if free > 0 {
+                let length = ::std::cmp::min(free, buf.len());
+                let offset = head as usize % RING_BYTES;
+                let first = ::std::cmp::min(length, RING_BYTES - offset);
+                unsafe {
+                    let data = self.mapping.data(self.mapping.send);
+                    ptr::copy_nonoverlapping(buf.as_ptr(), data.add(offset), first);
+                    ptr::copy_nonoverlapping(buf.as_ptr().add(first), data, length - first);
+                }
+                ring.head.store(head.wrapping_add(length as u32), Ordering::SeqCst);
+                if ring.reader_waiting.load(Ordering::SeqCst) != 0 {
+                    Mapping::wake(&ring.data_signal);
+                }
+                return Ok(length);
+            }
+            if self.mapping.peer_exited() {
+                ring.reader_gone.store(1, Ordering::SeqCst);
+                continue;
+            }
+            let signal = ring.space_signal.load(Ordering::SeqCst);
+            ring.writer_waiting.store(1, Ordering::SeqCst);
+            if ring.tail.load(Ordering::SeqCst) == tail && ring.reader_gone.load(Ordering::SeqCst) == 0 {
+                Mapping::wait(&ring.space_signal, signal);
+            }
+            ring.writer_waiting.store(0, Ordering::SeqCst);
+        }
+    }
+    fn flush(&mut self) -> Result<()> { Ok(()) }
+}
+
+impl Stream for SharedMemory {
+    fn try_clone(&self) -> Result<Self> {
+        Ok(SharedMemory { mapping: self.mapping.clone() })
+    }
+    fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        if nonblocking {
+            Err(io::Error::new(io::ErrorKind::InvalidInput, "shared memory streams are blocking"))
+        }
+        else {
+            Ok(())
+        }
+    }
+    fn shutdown_write(&mut self) -> Result<()> {
+        let ring = self.mapping.header(self.mapping.send);
+        ring.closed.store(1, Ordering::SeqCst);
+        Mapping::wake(&ring.data_signal);
+        Ok(())
+    }
+}
+
+/// Creates and sizes a new file for the rings of one connection, preferably in `/dev/shm`.
+fn create_file(name: &str) -> Result<(File, PathBuf)> {
+    let shm = PathBuf::from("/dev/shm");
+    let directory = if shm.is_dir() { shm } else { ::std::env::temp_dir() };
+    let path = directory.join(name);
+    let file = OpenOptions::new().read(true).write(true).create_new(true).open(&path)?;
+    file.set_len(mapping_bytes() as u64)?;
+    Ok((file, path))
+}
+
+/// Returns the rendezvous socket paths of `addresses`, if they all name shared memory connections.
+pub fn shm_paths(addresses: &[String]) -> Option<Vec<String>> {
+    addresses.iter().map(|address| address.strip_prefix(SHM_PREFIX).map(|path| path.to_string())).collect()
+}
+
+/// Creates shared memory connections between processes on this host.
+///
+/// The processes first connect through Unix domain sockets at `paths`, as `create_unix_sockets`
+/// does. For each pair, the process with the lower index then creates the file for their rings
+/// and sends its path over the socket; the other process maps it and acknowledges, after which
+/// the file is removed. The sockets carry no data afterwards, and are kept only to notice when
+/// the other process exits.
+pub fn create_shared_memory_streams(paths: Vec<String>, my_index: usize, noisy: bool) -> Result<Vec<Option<SharedMemory>>> {
+
+    let sockets = super::create_unix_sockets(paths, my_index, noisy)?;
+    sockets.into_iter().enumerate().map(|(index, socket)| {
+        socket.map(|mut socket| {
+            let mapping = if my_index < index {
+                let name = format!("timely-{}-{}-{}", ::std::process::id(), my_index, index);
+                let (file, path) = create_file(&name)?;
+                let mapping = offer(&file, &path, &mut socket);
+                let _ = ::std::fs::remove_file(&path);
+                mapping?
+            }
+            else {
+                accept(&mut socket)?
+            };
+            if noisy { println!("worker {}:\tshared memory with worker {}", my_index, index); }
+            SharedMemory::new(mapping)
+        }).transpose()
+    }).collect()
+}
+
+/// Maps a new file for the rings of a connection, and sends its path to the other process.
+fn offer(file: &File, path: &Path, socket: &mut UnixStream) -> Result<Mapping> {
+    let mapping = Mapping::map(file, 0, socket.try_clone()?)?;
+    let path = path.to_str().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "non-UTF-8 shared memory path"))?;
+    socket.write_all(&(path.len() as u64).to_le_bytes())?;
+    socket.write_all(path.as_bytes())?;
+    // the other process acknowledges once it has mapped the file, which may then be removed.
+    socket.read_exact(&mut [0u8])?;
+    Ok(mapping)
+}
+
+/// Maps the file whose path the other process sends, and acknowledges it.
+fn accept(socket: &mut UnixStream) -> Result<Mapping> {
+    let mut length = [0u8; 8];
+    socket.read_exact(&mut length)?;
+    let mut path = vec![0u8; u64::from_le_bytes(length) as usize];
+    socket.read_exact(&mut path)?;
+    let path = String::from_utf8(path).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    let file = OpenOptions::new().read(true).write(true).open(&path)?;
+    let mapping = Mapping::map(&file, 1, socket.try_clone()?)?;
+    socket.write_all(&[1u8])?;
+    Ok(mapping)
+}
@@ -0,0 +1,205 @@
+//! A `Stream` that encrypts and authenticates a TCP connection with TLS.
+//!
+//! Both ends of each connection present a certificate signed by a certificate authority common
+//! to the cluster, and only TLS 1.3 is accepted. The process that connects acts as the client.
+
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write, Result};
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rustls::{ClientConfig, ClientConnection, Connection, RootCertStore, ServerConfig, ServerConnection};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::server::WebPkiClientVerifier;
+
+use super::{Stream, TlsPaths};
+
+/// One end of a TLS session over TCP.
+///
+/// Handles made by `try_clone` share the session. Each handle reads and writes the socket outside
+/// the session's lock, so that a reader waiting for data never holds up a writer. Only writes send
+/// TLS records, so that with one writing handle the records reach the socket in order.
+pub struct TlsStream {
+    session: Arc<Mutex<Connection>>,
+    socket: TcpStream,
+    // TLS bytes read from the socket but not yet passed to the session.
+    incoming: Vec<u8>,
+    consumed: usize,
+}
+
+impl TlsStream {
+
+    /// Performs the client side of the TLS handshake over `socket`, expecting a certificate for `server`.
+    pub fn connect(socket: TcpStream, config: Arc<ClientConfig>, server: &str) -> Result<Self> {
+        let server = ServerName::try_from(server.to_string())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+        let session = ClientConnection::new(config, server).map_err(invalid_data)?;
+        TlsStream::handshake(Connection::Client(session), socket)
+    }
+
+    /// Performs the server side of the TLS handshake over `socket`.
+    pub fn accept(socket: TcpStream, config: Arc<ServerConfig>) -> Result<Self> {
+        let session = ServerConnection::new(config).map_err(invalid_data)?;
+        TlsStream::handshake(Connection::Server(session), socket)
+    }
+
+    fn handshake(mut session: Connection, mut socket: TcpStream) -> Result<Self> {
+        while session.is_handshaking() {
+            session.complete_io(&mut socket)?;
+        }
+        while session.wants_write() {
+            session.write_tls(&mut socket)?;
+        }
+        Ok(TlsStream { session: Arc::new(Mutex::new(session)), socket, incoming: Vec::new(), consumed: 0 })
+    }
+
+    /// Sends the TLS records the session has ready.
+    fn send_records(&mut self) -> Result<()> {
+        let mut records = Vec::new();
+        {
+            let mut session = lock(&self.session);
+            while session.wants_write() {
+                session.write_tls(&mut records)?;
+            }
+        }
+        self.socket.write_all(&records)
+    }
+}
+
+impl Read for TlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            {
+                let mut session = lock(&self.session);
+                match session.reader().read(buf) {
+                    Err(error) if error.kind() == io::ErrorKind::WouldBlock => { },
+                    result => return result,
+                }
+                // the session has no plaintext; pass it more of what has been read, if any.
+                if self.consumed < self.incoming.len() {
+                    let mut records = &self.incoming[self.consumed..];
+                    self.consumed += session.read_tls(&mut records)?;
+                    session.process_new_packets().map_err(invalid_data)?;
+                    continue;
+                }
+            }
+            self.incoming.resize(1 << 16, 0);
+            let read = self.socket.read(&mut self.incoming)?;
+            self.incoming.truncate(read);
+            self.consumed = 0;
+            if read == 0 {
+                // tell the session that the socket has closed, so that it reports the end of the stream.
+                let mut session = lock(&self.session);
+                session.read_tls(&mut io::empty())?;
+                session.process_new_packets().map_err(invalid_data)?;
+            }
+        }
+    }
+}
+
+impl Write for TlsStream {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let written = lock(&self.session).writer().write(buf)?;
+        self.send_records()?;
+        Ok(written)
+    }
+    fn flush(&mut self) -> Result<()> {
+        lock(&self.session).writer().flush()?;
+        self.send_records()?;
+        self.socket.flush()
+    }
+}
+
+impl Stream for TlsStream {
+    fn try_clone(&self) -> Result<Self> {
+        Ok(TlsStream { session: self.session.clone(), socket: self.socket.try_clone()?, incoming: Vec::new(), consumed: 0 })
+    }
+    fn set_nonblocking(&self, nonblocking: bool) -> Result<()> { self.socket.set_nonblocking(nonblocking) }
+    fn shutdown_write(&mut self) -> Result<()> {
+        lock(&self.session).send_close_notify();
+        self.send_records()?;
+        self.socket.shutdown(::std::net::Shutdown::Write)
+    }
+}
+
+fn lock(session: &Mutex<Connection>) -> MutexGuard<'_, Connection> {
+    session.lock().expect("TLS session lock poisoned")
+}
+
+fn invalid_data<E: ::std::error::Error+Send+Sync+'static>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// The client and server configurations of a process, read from the files at `paths`.
+pub fn configs(paths: &TlsPaths) -> Result<(Arc<ClientConfig>, Arc<ServerConfig>)> {
+
+    let certificates = read_certificates(&paths.certificate)?;
+    let key = read_key(&paths.key)?;
+    let mut authorities = RootCertStore::empty();
+    for authority in read_certificates(&paths.authority)? {
+        authorities.add(authority).map_err(invalid_data)?;
+    }
+    let authorities = Arc::new(authorities);
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+    let client = ClientConfig::builder_with_provider(provider.clone())
+        .with_protocol_versions(&[&rustls::version::TLS13]).map_err(invalid_data)?
+        .with_root_certificates(authorities.clone())
+        .with_client_auth_cert(certificates.clone(), key.clone_key()).map_err(invalid_data)?;
+
+    let verifier = WebPkiClientVerifier::builder_with_provider(authorities, provider.clone()).build().map_err(invalid_data)?;
+    let mut server = ServerConfig::builder_with_provider(provider)
+        .with_protocol_versions(&[&rustls::version::TLS13]).map_err(invalid_data)?
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(certificates, key).map_err(invalid_data)?;
+    // processes do not resume sessions.
+    server.send_tls13_tickets = 0;
+
+    Ok((Arc::new(client), Arc::new(server)))
+}
+
+fn read_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let certificates = rustls_pemfile::certs(&mut reader).collect::<Result<Vec<_>>>()?;
+    if certificates.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("no certificates in {}", path.display())));
+    }
+    Ok(certificates)
+}
+
+fn read_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    rustls_pemfile::private_key(&mut reader)?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("no private key in {}", path.display())))
+}
+
+/// Secures connections to other processes, made by `create_sockets`, with TLS.
+///
+/// Each process connects to the processes with lower indices, and so is the client of those
+/// sessions and the server of the others. The host part of each address is the name expected in
+/// that process's certificate.
+pub fn secure_sockets(
+    sockets: Vec<Option<TcpStream>>,
+    addresses: &[String],
+    my_index: usize,
+    paths: &TlsPaths)
+-> Result<Vec<Option<TlsStream>>>
+{
+    let (client, server) = configs(paths)?;
+    sockets.into_iter().enumerate().map(|(index, socket)| {
+        socket.map(|socket| {
+            if index < my_index {
+                let address = &addresses[index];
+                let host = address.rsplit_once(':').map(|(host, _port)| host).unwrap_or(address);
+                let host = host.trim_start_matches('[').trim_end_matches(']');
+                TlsStream::connect(socket, client.clone(), host)
+            }
+            else {
+                TlsStream::accept(socket, server.clone())
+            }
+        }).transpose()
+    }).collect()
+}
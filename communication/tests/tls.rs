@@ -0,0 +1,71 @@
+#![cfg(feature = "tls")]
+
+use std::net::TcpListener;
+use std::path::PathBuf;
+
+use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+use timely_communication::{Allocate, Configuration, Message};
+use timely_communication::networking::TlsPaths;
+
+/// Writes a certificate authority and a certificate for `localhost` signed by it to files named by `prefix`.
+fn certificates(prefix: &str) -> TlsPaths {
+    let directory = std::env::temp_dir();
+    let path = |name: &str| -> PathBuf { directory.join(format!("timely-tls-{}-{}-{}.pem", std::process::id(), prefix, name)) };
+
+    let authority_key = KeyPair::generate().unwrap();
+    let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let authority = params.self_signed(&authority_key).unwrap();
+
+    let key = KeyPair::generate().unwrap();
+    let certificate = CertificateParams::new(vec!["localhost".to_string()]).unwrap()
+        .signed_by(&key, &authority, &authority_key).unwrap();
+
+    let paths = TlsPaths { certificate: path("certificate"), key: path("key"), authority: path("authority") };
+    std::fs::write(&paths.certificate, certificate.pem()).unwrap();
+    std::fs::write(&paths.key, key.serialize_pem()).unwrap();
+    std::fs::write(&paths.authority, authority.pem()).unwrap();
+    paths
+}
+
+/// Runs two processes, as threads, which each send `length` copies of their index to the other.
+fn exchange(tls: Vec<TlsPaths>, length: usize) -> Vec<Result<Vec<usize>, String>> {
+    let addresses = (0..2).map(|_| {
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        format!("localhost:{}", port)
+    }).collect::<Vec<_>>();
+
+    let processes = tls.into_iter().enumerate().map(|(process, tls)| {
+        let addresses = addresses.clone();
+        std::thread::spawn(move || {
+            let config = Configuration::Cluster {
+                threads: 1, process, addresses, report: false, tls: Some(tls), log_fn: Box::new(|_| None),
+            };
+            timely_communication::initialize(config, move |mut allocator| {
+                let (mut senders, mut receiver) = allocator.allocate(0);
+                senders[1 - allocator.index()].send(Message::from_typed(vec![allocator.index(); length]));
+                loop {
+                    allocator.receive();
+                    let received = receiver.recv().map(|message| message.to_vec());
+                    allocator.release();
+                    if let Some(data) = received { break data; }
+                }
+            }).map(|guards| guards.join().pop().unwrap().unwrap())
+        })
+    }).collect::<Vec<_>>();
+
+    processes.into_iter().map(|process| process.join().unwrap()).collect()
+}
+
+#[test]
+fn tls_connections_carry_data() {
+    let paths = certificates("shared");
+    let received = exchange(vec![paths.clone(), paths], 1 << 20);
+    assert_eq!(received, vec![Ok(vec![1; 1 << 20]), Ok(vec![0; 1 << 20])]);
+}
+
+#[test]
+fn tls_connections_reject_other_authorities() {
+    let received = exchange(vec![certificates("first"), certificates("second")], 1);
+    assert!(received.iter().all(|result| result.is_err()));
+}
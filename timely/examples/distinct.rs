@@ -1,11 +1,7 @@
 extern crate timely;
 
-use std::collections::HashMap;
-
 use timely::dataflow::{InputHandle, ProbeHandle};
-use timely::dataflow::operators::{Input, Inspect, Probe};
-use timely::dataflow::operators::generic::operator::Operator;
-use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Input, Inspect, Probe, Distinct};
 
 fn main() {
     // initializes and runs a timely dataflow.
@@ -16,25 +12,8 @@ fn main() {
 
         // create a new input, exchange data, and inspect its output
         worker.dataflow::<usize,_,_>(|scope| {
-            let mut counts_by_time = HashMap::new();
             scope.input_from(&mut input)
-                .unary(Exchange::new(|x| *x), "Distinct", move |_, _|
-                    move |input, output| {
-                        input.for_each(|time, data| {
-                            let counts =
-                            counts_by_time
-                                .entry(time.time().clone())
-                                .or_insert(HashMap::new());
-                            let mut session = output.session(&time);
-                            for &datum in data.iter() {
-                                let count = counts.entry(datum).or_insert(0);
-                                if *count == 0 {
-                                    session.give(datum);
-                                }
-                                *count += 1;
-                            }
-                        })
-                    })
+                .distinct()
                 .inspect(move |x| println!("worker {}:\tvalue {}", index, x))
                 .probe_with(&mut probe);
         });
@@ -8,6 +8,7 @@ pub mod pushers;
 pub mod pullers;
 /// Parallelization contracts, describing how data must be exchanged between operators.
 pub mod pact;
+pub mod partitioner;
 
 /// The input to and output from timely dataflow communication channels.
 pub type Bundle<T, D> = crate::communication::Message<Message<T, D>>;
@@ -13,7 +13,9 @@ use crate::communication::{Push, Pull, Data};
 use crate::communication::allocator::thread::{ThreadPusher, ThreadPuller};
 
 use crate::worker::AsWorker;
-use crate::dataflow::channels::pushers::Exchange as ExchangePusher;
+use crate::dataflow::{Scope, Stream};
+use crate::dataflow::channels::pushers::{Exchange as ExchangePusher, Batcher};
+use crate::dataflow::channels::partitioner::{Partitioner, Range};
 use super::{Bundle, Message};
 
 use crate::logging::TimelyLogger as Logger;
@@ -42,16 +44,99 @@ impl<T: 'static, D: 'static> ParallelizationContract<T, D> for Pipeline {
     }
 }
 
+impl Pipeline {
+    /// Allocates a pipeline pact that forwards records in batches of at most `capacity` records.
+    ///
+    /// A `Pipeline` forwards the batches formed by the upstream operator's output, whose size is
+    /// `Message::default_length()`. This pact re-forms them into batches of `capacity` records for
+    /// this channel alone, and sends any partial batch once the upstream operator yields.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::generic::operator::Operator;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..1000u64).to_stream(scope)
+    ///                 .unary(Pipeline::with_capacity(10), "Batches", |_, _| |input, output| {
+    ///                     input.for_each(|time, data| {
+    ///                         output.session(&time).give(data.len());
+    ///                     });
+    ///                 })
+    ///                 .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![10; 100])]);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> BufferedPipeline {
+        assert!(capacity > 0, "pipeline batches must hold at least one record");
+        BufferedPipeline { capacity }
+    }
+}
+
+/// A direct connection that forwards records in batches of a configured size.
+pub struct BufferedPipeline { capacity: usize }
+impl<T: Eq+Data+Clone, D: Data+Clone> ParallelizationContract<T, D> for BufferedPipeline {
+    type Pusher = Batcher<T, D, LogPusher<T, D, ThreadPusher<Bundle<T, D>>>>;
+    type Puller = LogPuller<T, D, ThreadPuller<Bundle<T, D>>>;
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        let (pusher, puller) = Pipeline.connect(allocator, identifier, address, logging);
+        (Batcher::new(pusher, self.capacity), puller)
+    }
+}
+
 /// An exchange between multiple observers by data
-pub struct Exchange<D, F: FnMut(&D)->u64+'static> { hash_func: F, phantom: PhantomData<D>, }
+pub struct Exchange<D, F: FnMut(&D)->u64+'static> { hash_func: F, buffer_size: Option<usize>, phantom: PhantomData<D>, }
 impl<D, F: FnMut(&D)->u64> Exchange<D, F> {
     /// Allocates a new `Exchange` pact from a distribution function.
     pub fn new(func: F) -> Exchange<D, F> {
         Exchange {
             hash_func:  func,
+            buffer_size: None,
             phantom:    PhantomData,
         }
     }
+    /// Sets the number of records buffered for each worker before they are sent as one batch.
+    ///
+    /// By default records are sent in batches of `Message::default_length()`. Smaller batches
+    /// reach other workers sooner, and larger batches amortize the cost of sending each one.
+    /// The batches for a time are always sent once the upstream operator finishes with that time.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::channels::pact::Exchange;
+    /// use timely::dataflow::operators::generic::operator::Operator;
+    ///
+    /// let largest = Arc::new(Mutex::new(0));
+    /// let largest2 = largest.clone();
+    ///
+    /// timely::execute(timely::Configuration::Process(2), move |worker| {
+    ///     let largest = largest2.clone();
+    ///     worker.dataflow::<u64,_,_>(move |scope| {
+    ///         (0..1000u64).to_stream(scope)
+    ///                     .unary(Exchange::new(|x: &u64| *x).buffer_size(10), "Batches", |_, _| |input, output| {
+    ///                         input.for_each(|time, data| {
+    ///                             output.session(&time).give(data.len());
+    ///                         });
+    ///                     })
+    ///                     .inspect(move |len| {
+    ///                         let mut largest = largest.lock().unwrap();
+    ///                         *largest = ::std::cmp::max(*largest, *len);
+    ///                     });
+    ///     });
+    /// }).unwrap();
+    ///
+    /// assert_eq!(*largest.lock().unwrap(), 10);
+    /// ```
+    pub fn buffer_size(mut self, size: usize) -> Exchange<D, F> {
+        assert!(size > 0, "exchange buffers must hold at least one record");
+        self.buffer_size = Some(size);
+        self
+    }
 }
 
 // Exchange uses a `Box<Pushable>` because it cannot know what type of pushable will return from the allocator.
@@ -63,7 +148,286 @@ impl<T: Eq+Data+Clone, D: Data+Clone, F: FnMut(&D)->u64+'static> Parallelization
     fn connect<A: AsWorker>(mut self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
         let (senders, receiver) = allocator.allocate::<Message<T, D>>(identifier, address);
         let senders = senders.into_iter().enumerate().map(|(i,x)| LogPusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
-        (Box::new(ExchangePusher::new(senders, move |_, d| (self.hash_func)(d))), Box::new(LogPuller::new(receiver, allocator.index(), identifier, logging.clone())))
+        let capacity = self.buffer_size.unwrap_or_else(Message::<T, D>::default_length);
+        (Box::new(ExchangePusher::with_capacity(senders, capacity, move |_, d| (self.hash_func)(d))), Box::new(LogPuller::new(receiver, allocator.index(), identifier, logging.clone())))
+    }
+}
+
+/// An exchange of pairs whose first element is a precomputed routing key.
+///
+/// Each record is routed by its key, modulo the number of workers, as `Exchange` would route it
+/// by the result of its hash function. Keys computed once, for example by `pre_hashed`, can
+/// be used by several exchanges without evaluating the hash function again for each record.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::{ToStream, PreHashed, Inspect};
+/// use timely::dataflow::operators::generic::operator::Operator;
+/// use timely::dataflow::channels::pact::ExchangeByKey;
+///
+/// timely::example(|scope| {
+///     (0..10u64).to_stream(scope)
+///               .pre_hashed(|x| *x)
+///               .unary(ExchangeByKey, "Route", |_, _| |input, output| {
+///                   input.for_each(|time, data| {
+///                       output.session(&time).give_vec(&mut data.replace(Vec::new()));
+///                   });
+///               })
+///               .inspect(|(key, x)| assert_eq!(key, x));
+/// });
+/// ```
+pub struct ExchangeByKey;
+
+impl<T: Eq+Data+Clone, D: Data+Clone> ParallelizationContract<T, (u64, D)> for ExchangeByKey {
+    type Pusher = Box<dyn Push<Bundle<T, (u64, D)>>>;
+    type Puller = Box<dyn Pull<Bundle<T, (u64, D)>>>;
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        Exchange::new(|d: &(u64, D)| d.0).connect(allocator, identifier, address, logging)
+    }
+}
+
+/// An exchange that spreads records evenly across workers, without regard to their contents.
+///
+/// Each worker sends its records to the workers in turn, starting from the worker after itself,
+/// so that each receives an equal share however skewed the records' keys. This suits per-record
+/// work in which records need not meet others with the same key.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::ToStream;
+/// use timely::dataflow::operators::generic::operator::Operator;
+/// use timely::dataflow::channels::pact::Balance;
+///
+/// timely::example(|scope| {
+///     (0..10u64).to_stream(scope)
+///               .unary(Balance, "Forward", |_, _| |input, output| {
+///                   input.for_each(|time, data| {
+///                       output.session(&time).give_vec(&mut data.replace(Vec::new()));
+///                   });
+///               });
+/// });
+/// ```
+pub struct Balance;
+
+impl<T: Eq+Data+Clone, D: Data+Clone> ParallelizationContract<T, D> for Balance {
+    type Pusher = Box<dyn Push<Bundle<T, D>>>;
+    type Puller = Box<dyn Pull<Bundle<T, D>>>;
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        let mut next = allocator.index() as u64;
+        Exchange::new(move |_: &D| { next = next.wrapping_add(1); next }).connect(allocator, identifier, address, logging)
+    }
+}
+
+/// An exchange that spreads records evenly across the workers in the sending worker's process.
+///
+/// Records are sent to the workers of the process in turn, as `Balance` sends them to all
+/// workers, and so never cross the network. Where all workers share a process, as with
+/// `Configuration::Process`, the two contracts route records identically.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::ToStream;
+/// use timely::dataflow::operators::generic::operator::Operator;
+/// use timely::dataflow::channels::pact::LocalBalance;
+///
+/// timely::example(|scope| {
+///     (0..10u64).to_stream(scope)
+///               .unary(LocalBalance, "Forward", |_, _| |input, output| {
+///                   input.for_each(|time, data| {
+///                       output.session(&time).give_vec(&mut data.replace(Vec::new()));
+///                   });
+///               });
+/// });
+/// ```
+pub struct LocalBalance;
+
+impl<T: Eq+Data+Clone, D: Data+Clone> ParallelizationContract<T, D> for LocalBalance {
+    type Pusher = Box<dyn Push<Bundle<T, D>>>;
+    type Puller = Box<dyn Pull<Bundle<T, D>>>;
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        let local = allocator.local_peers();
+        let mut next = allocator.index() - local.start;
+        let count = local.end - local.start;
+        Exchange::new(move |_: &D| { next = (next + 1) % count; (local.start + next) as u64 }).connect(allocator, identifier, address, logging)
+    }
+}
+
+/// An exchange between multiple observers, routed by a `Partitioner`.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::ToStream;
+/// use timely::dataflow::operators::generic::operator::Operator;
+/// use timely::dataflow::channels::pact::Partitioned;
+/// use timely::dataflow::channels::partitioner::Range;
+///
+/// timely::example(|scope| {
+///     let pact = Partitioned::new(Range::new(vec![5], |x: &u64| *x));
+///     (0..10u64).to_stream(scope)
+///               .unary(pact, "Forward", |_, _| |input, output| {
+///                   input.for_each(|time, data| {
+///                       output.session(&time).give_vec(&mut data.replace(Vec::new()));
+///                   });
+///               });
+/// });
+/// ```
+pub struct Partitioned<D, P: Partitioner<D>> { partitioner: P, phantom: PhantomData<D>, }
+impl<D, P: Partitioner<D>> Partitioned<D, P> {
+    /// Allocates a new `Partitioned` pact from a partitioner.
+    pub fn new(partitioner: P) -> Partitioned<D, P> {
+        Partitioned {
+            partitioner,
+            phantom:    PhantomData,
+        }
+    }
+}
+
+impl<T: Eq+Data+Clone, D: Data+Clone, P: Partitioner<D>+'static> ParallelizationContract<T, D> for Partitioned<D, P> {
+    type Pusher = Box<dyn Push<Bundle<T, D>>>;
+    type Puller = Box<dyn Pull<Bundle<T, D>>>;
+    fn connect<A: AsWorker>(mut self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        let (senders, receiver) = allocator.allocate::<Message<T, D>>(identifier, address);
+        let peers = senders.len();
+        let senders = senders.into_iter().enumerate().map(|(i,x)| LogPusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
+        (Box::new(ExchangePusher::new(senders, move |_, d| self.partitioner.partition(d, peers) as u64)), Box::new(LogPuller::new(receiver, allocator.index(), identifier, logging.clone())))
+    }
+}
+
+/// An exchange that routes records by ranges of a key.
+///
+/// Worker `i` receives the records whose keys are at least boundary `i - 1` and less than boundary
+/// `i`. The boundaries can be supplied directly, or chosen from a sample of keys. Records are
+/// moved exactly as with `Exchange`, and only the choice of worker differs.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::ToStream;
+/// use timely::dataflow::operators::generic::operator::Operator;
+/// use timely::dataflow::channels::pact::RangeExchange;
+///
+/// timely::example(|scope| {
+///     let pact = RangeExchange::new(vec![5], |x: &u64| *x);
+///     (0..10u64).to_stream(scope)
+///               .unary(pact, "Forward", |_, _| |input, output| {
+///                   input.for_each(|time, data| {
+///                       output.session(&time).give_vec(&mut data.replace(Vec::new()));
+///                   });
+///               });
+/// });
+/// ```
+pub struct RangeExchange<D, K: Ord, F: FnMut(&D)->K+'static> { partitioned: Partitioned<D, Range<K, F>>, }
+impl<D, K: Ord, F: FnMut(&D)->K> RangeExchange<D, K, F> {
+    /// Allocates a new `RangeExchange` pact from increasing boundaries and a key function.
+    pub fn new(boundaries: Vec<K>, key: F) -> RangeExchange<D, K, F> {
+        RangeExchange { partitioned: Partitioned::new(Range::new(boundaries, key)) }
+    }
+    /// Allocates a new `RangeExchange` pact whose boundaries split a sample of keys into `peers`
+    /// ranges of roughly equal size.
+    ///
+    /// All workers must supply the same sample, so that they choose the same boundaries.
+    pub fn from_sample(sample: Vec<K>, peers: usize, key: F) -> RangeExchange<D, K, F> {
+        RangeExchange { partitioned: Partitioned::new(Range::from_sample(sample, peers, key)) }
+    }
+}
+
+impl<T: Eq+Data+Clone, D: Data+Clone, K: Ord+'static, F: FnMut(&D)->K+'static> ParallelizationContract<T, D> for RangeExchange<D, K, F> {
+    type Pusher = Box<dyn Push<Bundle<T, D>>>;
+    type Puller = Box<dyn Pull<Bundle<T, D>>>;
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        self.partitioned.connect(allocator, identifier, address, logging)
+    }
+}
+
+/// A key function identified by its type, by which streams can be partitioned among workers.
+///
+/// Each implementing type stands for one way of routing records, and streams exchanged by it
+/// remember its type, so that a later exchange by the same key can leave records in place.
+pub trait KeyHash<D>: 'static {
+    /// The hash of the key of `datum`, which determines the worker to which it is routed.
+    fn hash(datum: &D) -> u64;
+}
+
+/// An exchange by the key `K`, which is skipped if the stream is already partitioned by `K`.
+///
+/// If the stream `is_partitioned_by::<K>()`, records stay at their worker as with `Pipeline`,
+/// and otherwise they are exchanged by `K::hash` as with `Exchange`. In debug builds, records
+/// that stay in place are checked to be at the worker `K` routes them to.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+/// use timely::dataflow::channels::pact::{KeyHash, KeyedPipeline};
+///
+/// struct Parity;
+/// impl KeyHash<u64> for Parity {
+///     fn hash(x: &u64) -> u64 { x % 2 }
+/// }
+///
+/// struct Value;
+/// impl KeyHash<u64> for Value {
+///     fn hash(x: &u64) -> u64 { *x }
+/// }
+///
+/// timely::example(|scope| {
+///     let stream = (0..10u64).to_stream(scope);
+///     assert!(!KeyedPipeline::<Parity, _>::new(&stream).is_pipeline());
+///
+///     let keyed = stream.exchange_by::<Parity>();
+///     assert!(KeyedPipeline::<Parity, _>::new(&keyed).is_pipeline());
+///     assert!(!KeyedPipeline::<Value, _>::new(&keyed).is_pipeline());
+/// });
+/// ```
+pub struct KeyedPipeline<K, D> {
+    pipeline: bool,
+    phantom: PhantomData<(K, D)>,
+}
+impl<K: KeyHash<D>, D> KeyedPipeline<K, D> {
+    /// Allocates a new `KeyedPipeline` pact for `stream`, exchanging by `K` if `stream` is not
+    /// already partitioned by `K`.
+    pub fn new<G: Scope>(stream: &Stream<G, D>) -> KeyedPipeline<K, D> {
+        KeyedPipeline { pipeline: stream.is_partitioned_by::<K>(), phantom: PhantomData }
+    }
+    /// Indicates that the pact will not move records between workers.
+    pub fn is_pipeline(&self) -> bool {
+        self.pipeline
+    }
+}
+
+impl<T: Eq+Data+Clone, D: Data+Clone, K: KeyHash<D>> ParallelizationContract<T, D> for KeyedPipeline<K, D> {
+    type Pusher = Box<dyn Push<Bundle<T, D>>>;
+    type Puller = Box<dyn Pull<Bundle<T, D>>>;
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        if self.pipeline {
+            let (index, peers) = (allocator.index(), allocator.peers());
+            let (pusher, puller) = Pipeline.connect(allocator, identifier, address, logging);
+            (Box::new(KeyedPusher::<K, _> { pusher, index, peers, phantom: PhantomData }), Box::new(puller))
+        }
+        else {
+            Exchange::new(|datum: &D| K::hash(datum)).connect(allocator, identifier, address, logging)
+        }
+    }
+}
+
+/// Checks in debug builds that records pushed along a pipeline are at the worker `K` routes them to.
+struct KeyedPusher<K, P> {
+    pusher: P,
+    index: usize,
+    peers: usize,
+    phantom: PhantomData<K>,
+}
+
+impl<T: Clone, D: Clone, K: KeyHash<D>, P: Push<Bundle<T, D>>> Push<Bundle<T, D>> for KeyedPusher<K, P> {
+    fn push(&mut self, message: &mut Option<Bundle<T, D>>) {
+        if cfg!(debug_assertions) {
+            if let Some(message) = message {
+                let (index, peers) = (self.index, self.peers as u64);
+                assert!(
+                    message.as_mut().data.iter().all(|datum| (K::hash(datum) % peers) as usize == index),
+                    "stream marked as partitioned by a key has records at other workers"
+                );
+            }
+        }
+        self.pusher.push(message);
     }
 }
 
@@ -112,6 +476,9 @@ impl<T, D, P: Push<Bundle<T, D>>> Push<Bundle<T, D>> for LogPusher<T, D, P> {
                 seq_no: self.counter-1,
                 length: bundle.data.len(),
             }));
+
+            #[cfg(feature = "tracing")]
+            tracing::trace!(channel = self.channel, source = self.source, target = self.target, length = bundle.data.len(), "send");
         }
         self.pusher.push(pair);
     }
@@ -153,6 +520,9 @@ impl<T, D, P: Pull<Bundle<T, D>>> Pull<Bundle<T, D>> for LogPuller<T, D, P> {
                 seq_no: bundle.seq,
                 length: bundle.data.len(),
             }));
+
+            #[cfg(feature = "tracing")]
+            tracing::trace!(channel, source = bundle.from, target, length = bundle.data.len(), "recv");
         }
         result
     }
@@ -0,0 +1,188 @@
+//! Strategies for routing records to workers.
+//!
+//! A `Partitioner` chooses the worker to which each record is sent, and can be used to exchange
+//! data with the `Partitioned` parallelization contract. The `Exchange` contract corresponds to
+//! the `Hashed` partitioner.
+
+use std::hash::Hash;
+use std::collections::HashMap;
+
+/// Chooses the worker to which each record is sent.
+pub trait Partitioner<D> {
+    /// The index of the worker, less than `peers`, to which `datum` should be sent.
+    fn partition(&mut self, datum: &D, peers: usize) -> usize;
+}
+
+/// Routes records by a hash of each record, modulo the number of workers.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::channels::partitioner::{Partitioner, Hashed};
+///
+/// let mut partitioner = Hashed::new(|x: &u64| *x);
+/// assert_eq!(partitioner.partition(&7, 4), 3);
+/// assert_eq!(partitioner.partition(&8, 4), 0);
+/// ```
+pub struct Hashed<F> {
+    hash: F,
+}
+
+impl<F> Hashed<F> {
+    /// Allocates a new partitioner from a hash function.
+    pub fn new(hash: F) -> Self {
+        Hashed { hash }
+    }
+}
+
+impl<D, F: FnMut(&D)->u64> Partitioner<D> for Hashed<F> {
+    fn partition(&mut self, datum: &D, peers: usize) -> usize {
+        ((self.hash)(datum) % peers as u64) as usize
+    }
+}
+
+/// Routes records by ranges of a key, with each boundary starting the range of the next worker.
+///
+/// Keys less than the first boundary are sent to worker zero, keys at least the first boundary
+/// and less than the second to worker one, and so on. Keys beyond the ranges of all workers are
+/// sent to the last worker.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::channels::partitioner::{Partitioner, Range};
+///
+/// let mut partitioner = Range::new(vec![10, 20], |x: &u64| *x);
+/// assert_eq!(partitioner.partition(&5, 3), 0);
+/// assert_eq!(partitioner.partition(&10, 3), 1);
+/// assert_eq!(partitioner.partition(&25, 3), 2);
+/// assert_eq!(partitioner.partition(&25, 2), 1);
+/// ```
+pub struct Range<K, F> {
+    boundaries: Vec<K>,
+    key: F,
+}
+
+impl<K: Ord, F> Range<K, F> {
+    /// Allocates a new partitioner from increasing boundaries and a key function.
+    pub fn new(boundaries: Vec<K>, key: F) -> Self {
+        assert!(boundaries.windows(2).all(|x| x[0] <= x[1]), "range boundaries must be increasing");
+        Range { boundaries, key }
+    }
+
+    /// Allocates a new partitioner whose boundaries split a sample of keys into `parts` ranges of
+    /// roughly equal size.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::channels::partitioner::{Partitioner, Range};
+    ///
+    /// let mut partitioner = Range::from_sample((0..100).map(|x| x * x).collect(), 4, |x: &u64| *x);
+    /// assert_eq!(partitioner.partition(&0, 4), 0);
+    /// assert_eq!(partitioner.partition(&2600, 4), 2);
+    /// assert_eq!(partitioner.partition(&9801, 4), 3);
+    /// ```
+    pub fn from_sample(mut sample: Vec<K>, parts: usize, key: F) -> Self {
+        sample.sort();
+        let len = sample.len();
+        let indices = (1 .. parts).map(|part| part * len / parts).collect::<Vec<_>>();
+        let boundaries = sample.into_iter()
+                               .enumerate()
+                               .filter(|(index, _)| indices.binary_search(index).is_ok())
+                               .map(|(_, key)| key)
+                               .collect();
+        Range { boundaries, key }
+    }
+}
+
+impl<D, K: Ord, F: FnMut(&D)->K> Partitioner<D> for Range<K, F> {
+    fn partition(&mut self, datum: &D, peers: usize) -> usize {
+        let key = (self.key)(datum);
+        let index = self.boundaries.iter().take_while(|boundary| **boundary <= key).count();
+        ::std::cmp::min(index, peers - 1)
+    }
+}
+
+/// Routes records by an explicit table from keys to workers.
+///
+/// Records whose keys are not in the table are routed by `hash` of their key.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashMap;
+/// use timely::dataflow::channels::partitioner::{Partitioner, Table};
+///
+/// // send the records of the heavily loaded key 4 to a worker of their own.
+/// let mut routes = HashMap::new();
+/// routes.insert(4, 3);
+/// let mut partitioner = Table::new(routes, |x: &(u64, &str)| x.0, |key: &u64| *key % 3);
+/// assert_eq!(partitioner.partition(&(4, "hot"), 4), 3);
+/// assert_eq!(partitioner.partition(&(5, "cold"), 4), 2);
+/// ```
+pub struct Table<K, F, H> {
+    routes: HashMap<K, usize>,
+    key: F,
+    hash: H,
+}
+
+impl<K: Hash+Eq, F, H> Table<K, F, H> {
+    /// Allocates a new partitioner from a table of routes, a key function, and a fallback hash.
+    pub fn new(routes: HashMap<K, usize>, key: F, hash: H) -> Self {
+        Table { routes, key, hash }
+    }
+}
+
+impl<D, K: Hash+Eq, F: FnMut(&D)->K, H: FnMut(&K)->u64> Partitioner<D> for Table<K, F, H> {
+    fn partition(&mut self, datum: &D, peers: usize) -> usize {
+        let key = (self.key)(datum);
+        match self.routes.get(&key) {
+            Some(&index) => {
+                assert!(index < peers, "routing table names worker {} of {}", index, peers);
+                index
+            },
+            None => ((self.hash)(&key) % peers as u64) as usize,
+        }
+    }
+}
+
+/// Routes records by rendezvous (highest random weight) hashing.
+///
+/// Each record is sent to the worker with the highest weight derived from the record's hash and
+/// the worker's index. When the number of workers changes, only the records whose chosen worker
+/// was added or removed move to another worker.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::channels::partitioner::{Partitioner, Rendezvous};
+///
+/// let mut partitioner = Rendezvous::new(|x: &u64| *x);
+/// let moved = (0..1000u64).filter(|x| partitioner.partition(x, 4) != partitioner.partition(x, 5)).count();
+/// // about one fifth of the records move to the new worker, and no others.
+/// assert!(100 < moved && moved < 300);
+/// assert!((0..1000u64).all(|x| {
+///     let index = partitioner.partition(&x, 5);
+///     index == 4 || index == partitioner.partition(&x, 4)
+/// }));
+/// ```
+pub struct Rendezvous<F> {
+    hash: F,
+}
+
+impl<F> Rendezvous<F> {
+    /// Allocates a new partitioner from a hash function.
+    pub fn new(hash: F) -> Self {
+        Rendezvous { hash }
+    }
+}
+
+impl<D, F: FnMut(&D)->u64> Partitioner<D> for Rendezvous<F> {
+    fn partition(&mut self, datum: &D, peers: usize) -> usize {
+        let hash = (self.hash)(datum);
+        (0 .. peers).max_by_key(|&index| mix(hash ^ mix(index as u64))).unwrap_or(0)
+    }
+}
+
+/// Scrambles the bits of `x` (the finalizer of splitmix64).
+pub(crate) fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
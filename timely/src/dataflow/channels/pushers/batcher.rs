@@ -0,0 +1,59 @@
+//! A pusher that re-forms the batches pushed at it into batches of a fixed size.
+
+use crate::Data;
+use crate::communication::Push;
+use crate::dataflow::channels::{Bundle, Message};
+
+/// Re-forms the batches pushed at it into batches of at most `capacity` records.
+///
+/// Records are held until a batch fills, the time changes, or a `None` is pushed, which flushes
+/// any held records and is then forwarded.
+pub struct Batcher<T, D, P: Push<Bundle<T, D>>> {
+    pusher: P,
+    buffer: Vec<D>,
+    capacity: usize,
+    current: Option<T>,
+}
+
+impl<T: Clone, D, P: Push<Bundle<T, D>>> Batcher<T, D, P> {
+    /// Allocates a new `Batcher` sending batches of at most `capacity` records to `pusher`.
+    pub fn new(pusher: P, capacity: usize) -> Batcher<T, D, P> {
+        assert!(capacity > 0, "batches must hold at least one record");
+        Batcher {
+            pusher,
+            buffer: Vec::with_capacity(capacity),
+            capacity,
+            current: None,
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.buffer.is_empty() {
+            if let Some(ref time) = self.current {
+                Message::push_at(&mut self.buffer, time.clone(), &mut self.pusher);
+            }
+        }
+    }
+}
+
+impl<T: Eq+Data, D: Data, P: Push<Bundle<T, D>>> Push<Bundle<T, D>> for Batcher<T, D, P> {
+    fn push(&mut self, message: &mut Option<Bundle<T, D>>) {
+        if let Some(message) = message {
+            let message = message.as_mut();
+            if self.current.as_ref() != Some(&message.time) {
+                self.flush();
+                self.current = Some(message.time.clone());
+            }
+            for datum in message.data.drain(..) {
+                self.buffer.push(datum);
+                if self.buffer.len() >= self.capacity {
+                    self.flush();
+                }
+            }
+        }
+        else {
+            self.flush();
+            self.pusher.push(&mut None);
+        }
+    }
+}
@@ -106,11 +106,11 @@ impl<'a, T, D, P: Push<Bundle<T, D>>+'a> Session<'a, T, D, P>  where T: Eq+Clone
             self.give(item);
         }
     }
-    /// Provides a fully formed `Content<D>` message for senders which can use this type.
+    /// Provides a fully formed `Vec<D>` batch of records for senders which can use this type.
     ///
-    /// The `Content` type is the backing memory for communication in timely, and it can
-    /// often be more efficient to re-use this memory rather than have timely allocate
-    /// new backing memory.
+    /// The vector is sent as the backing memory of a message, and it can often be more
+    /// efficient to re-use this memory rather than have timely allocate new backing memory.
+    /// The vector is left empty, possibly holding an allocation returned by the recipient.
     #[inline]
     pub fn give_vec(&mut self, message: &mut Vec<D>) {
         if message.len() > 0 {
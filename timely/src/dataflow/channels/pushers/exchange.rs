@@ -9,6 +9,7 @@ use crate::dataflow::channels::{Bundle, Message};
 pub struct Exchange<T, D, P: Push<Bundle<T, D>>, H: FnMut(&T, &D) -> u64> {
     pushers: Vec<P>,
     buffers: Vec<Vec<D>>,
+    capacity: usize,
     current: Option<T>,
     hash_func: H,
 }
@@ -16,14 +17,20 @@ pub struct Exchange<T, D, P: Push<Bundle<T, D>>, H: FnMut(&T, &D) -> u64> {
 impl<T: Clone, D, P: Push<Bundle<T, D>>, H: FnMut(&T, &D)->u64>  Exchange<T, D, P, H> {
     /// Allocates a new `Exchange` from a supplied set of pushers and a distribution function.
     pub fn new(pushers: Vec<P>, key: H) -> Exchange<T, D, P, H> {
+        Self::with_capacity(pushers, Message::<T, D>::default_length(), key)
+    }
+    /// Allocates a new `Exchange` which sends records to each pushee in batches of `capacity`.
+    pub fn with_capacity(pushers: Vec<P>, capacity: usize, key: H) -> Exchange<T, D, P, H> {
+        assert!(capacity > 0, "exchange batches must hold at least one record");
         let mut buffers = vec![];
         for _ in 0..pushers.len() {
-            buffers.push(Vec::with_capacity(Message::<T, D>::default_length()));
+            buffers.push(Vec::with_capacity(capacity));
         }
         Exchange {
             pushers,
             hash_func: key,
             buffers,
+            capacity,
             current: None,
         }
     }
@@ -65,7 +72,7 @@ impl<T: Eq+Data, D: Data, P: Push<Bundle<T, D>>, H: FnMut(&T, &D)->u64> Push<Bun
                     let index = (((self.hash_func)(time, &datum)) & mask) as usize;
 
                     self.buffers[index].push(datum);
-                    if self.buffers[index].len() == self.buffers[index].capacity() {
+                    if self.buffers[index].len() >= self.capacity {
                         self.flush(index);
                     }
 
@@ -83,7 +90,7 @@ impl<T: Eq+Data, D: Data, P: Push<Bundle<T, D>>, H: FnMut(&T, &D)->u64> Push<Bun
                 for datum in data.drain(..) {
                     let index = (((self.hash_func)(time, &datum)) % self.pushers.len() as u64) as usize;
                     self.buffers[index].push(datum);
-                    if self.buffers[index].len() == self.buffers[index].capacity() {
+                    if self.buffers[index].len() >= self.capacity {
                         self.flush(index);
                     }
                 }
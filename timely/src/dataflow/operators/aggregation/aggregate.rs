@@ -60,6 +60,30 @@ pub trait Aggregate<S: Scope, K: ExchangeData+Hash, V: ExchangeData> {
     ///         .inspect(|x| assert!(*x == (0, 5) || *x == (1, 5)));
     /// });
     /// ```
+    ///
+    /// Aggregates are maintained independently for each time, and each is emitted at its
+    /// time once that time is complete.
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Delay, Capture};
+    /// use timely::dataflow::operators::aggregation::Aggregate;
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///
+    ///     (0..10u64).to_stream(scope)
+    ///         .delay(|x, _time| x / 5)
+    ///         .map(|x| (x % 2, x))
+    ///         .aggregate(
+    ///             |_key, val, agg| { *agg += val; },
+    ///             |key, agg: u64| (key, agg),
+    ///             |key| *key
+    ///         )
+    ///         .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![(0, 6), (1, 4)]), (1, vec![(0, 14), (1, 21)])]);
+    /// ```
     fn aggregate<R: Data, D: Default+'static, F: Fn(&K, V, &mut D)+'static, E: Fn(K, D)->R+'static, H: Fn(&K)->u64+'static>(
         &self,
         fold: F,
@@ -10,9 +10,16 @@
 //!
 //! The two methods are often combined, using first `Aggregate` to reduce the volume of information, and then
 //! `StateMachine` to track an accumulation across timestamps.
+//!
+//! `StateMap` maps each keyed record using a state for its key, and evicts the states of keys that
+//! have been idle for a number of epochs.
+//!
+//! `Scan` folds records into a running state in timestamp order, and reports the state as each time completes.
 
 pub use self::aggregate::Aggregate;
-pub use self::state_machine::StateMachine;
+pub use self::state_machine::{StateMachine, StateMap};
+pub use self::scan::Scan;
 
 pub mod state_machine;
 pub mod aggregate;
+pub mod scan;
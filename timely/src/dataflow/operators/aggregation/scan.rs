@@ -0,0 +1,152 @@
+//! Running accumulation of records across timestamps
+use std::collections::HashMap;
+
+use crate::{Data, ExchangeData};
+use crate::order::TotalOrder;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::channels::pact::{Pipeline, Exchange};
+
+/// Running accumulation of records across timestamps
+///
+/// Extension methods maintaining a state that is updated with each record, in timestamp order,
+/// and reporting the state as each timestamp completes. The timestamps must be totally ordered,
+/// so that the order in which they are applied is well defined.
+pub trait Scan<G: Scope, D: Data> where G::Timestamp: TotalOrder {
+    /// Folds records into a running state, and produces the state at each time with records,
+    /// once that time is complete.
+    ///
+    /// The records at each time are folded in after those of all earlier times, and in the order
+    /// in which they arrive. Each worker maintains its own state from its own records; use
+    /// `scan_global` to accumulate the records of all workers.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Capture};
+    /// use timely::dataflow::operators::aggregation::Scan;
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10u64).to_stream(scope)
+    ///               .delay(|x, _time| x / 4)
+    ///               .scan(0, |sum, x| *sum += x)
+    ///               .capture()
+    /// });
+    ///
+    /// // the prefix sums through each of times zero, one, and two.
+    /// assert_eq!(captured.extract(), vec![(0, vec![6]), (1, vec![28]), (2, vec![45])]);
+    /// ```
+    fn scan<S: Data, F: FnMut(&mut S, D)+'static>(&self, initial: S, fold: F) -> Stream<G, S>;
+
+    /// Folds the records of all workers into a running state, and produces the state at each
+    /// time with records, at worker zero, once that time is complete.
+    ///
+    /// The records are accumulated in two phases: each worker folds its records at a time into
+    /// a partial state starting from `S::default()`, and worker zero merges the partial states
+    /// of each time, in timestamp order, into the running state with `combine`. The result is
+    /// independent of the order in which partial states are merged if `combine` is commutative.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Delay, Capture};
+    /// use timely::dataflow::operators::aggregation::Scan;
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (send, recv) = ::std::sync::mpsc::channel();
+    /// let send = Arc::new(Mutex::new(send));
+    ///
+    /// timely::execute(timely::Configuration::Process(2), move |worker| {
+    ///     let send = send.lock().unwrap().clone();
+    ///     worker.dataflow::<u64,_,_>(move |scope| {
+    ///         // each worker counts the records through each time.
+    ///         (0..10u64).to_stream(scope)
+    ///                   .delay(|x, _time| x / 4)
+    ///                   .scan_global(0, |count, _x| *count += 1, |count, partial| *count += partial)
+    ///                   .capture_into(send);
+    ///     });
+    /// }).unwrap();
+    ///
+    /// assert_eq!(recv.extract(), vec![(0, vec![8]), (1, vec![16]), (2, vec![20])]);
+    /// ```
+    fn scan_global<S, F, C>(&self, initial: S, fold: F, combine: C) -> Stream<G, S>
+    where
+        S: ExchangeData+Default,
+        F: FnMut(&mut S, D)+'static,
+        C: FnMut(&mut S, S)+'static;
+}
+
+impl<G: Scope, D: Data> Scan<G, D> for Stream<G, D> where G::Timestamp: TotalOrder {
+
+    fn scan<S: Data, F: FnMut(&mut S, D)+'static>(&self, initial: S, mut fold: F) -> Stream<G, S> {
+
+        let mut state = initial;
+        let mut pending = HashMap::new();
+        let mut vector = Vec::new();
+        self.unary_notify(Pipeline, "Scan", vec![], move |input, output, notificator| {
+
+            // stash records until their time is complete.
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                pending.entry(time.time().clone()).or_insert_with(Vec::new).append(&mut vector);
+                notificator.notify_at(time.retain());
+            });
+
+            // completed times are presented in order; fold in their records and report the state.
+            notificator.for_each(|time,_,_| {
+                if let Some(records) = pending.remove(time.time()) {
+                    for record in records {
+                        fold(&mut state, record);
+                    }
+                    output.session(&time).give(state.clone());
+                }
+            });
+        })
+    }
+
+    fn scan_global<S, F, C>(&self, initial: S, mut fold: F, mut combine: C) -> Stream<G, S>
+    where
+        S: ExchangeData+Default,
+        F: FnMut(&mut S, D)+'static,
+        C: FnMut(&mut S, S)+'static {
+
+        // fold each worker's records at each time into a partial state.
+        let mut partials = HashMap::new();
+        let mut vector = Vec::new();
+        let partial = self.unary_notify(Pipeline, "ScanPartial", vec![], move |input, output, notificator| {
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                let partial = partials.entry(time.time().clone()).or_insert_with(S::default);
+                for record in vector.drain(..) {
+                    fold(partial, record);
+                }
+                notificator.notify_at(time.retain());
+            });
+            notificator.for_each(|time,_,_| {
+                if let Some(partial) = partials.remove(time.time()) {
+                    output.session(&time).give(partial);
+                }
+            });
+        });
+
+        // merge the partial states of all workers into the running state, at worker zero.
+        let mut state = initial;
+        let mut pending = HashMap::new();
+        let mut vector = Vec::new();
+        partial.unary_notify(Exchange::new(|_| 0), "ScanGlobal", vec![], move |input, output, notificator| {
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                pending.entry(time.time().clone()).or_insert_with(Vec::new).append(&mut vector);
+                notificator.notify_at(time.retain());
+            });
+            notificator.for_each(|time,_,_| {
+                if let Some(partials) = pending.remove(time.time()) {
+                    for partial in partials {
+                        combine(&mut state, partial);
+                    }
+                    output.session(&time).give(state.clone());
+                }
+            });
+        })
+    }
+}
@@ -111,3 +111,97 @@ impl<S: Scope, K: ExchangeData+Hash+Eq, V: ExchangeData> StateMachine<S, K, V> f
         })
     }
 }
+
+/// Provides the `state_map` method.
+pub trait StateMap<S: Scope<Timestamp=u64>, K: ExchangeData+Hash+Eq, V: ExchangeData> {
+    /// Maps each record using a state for its key, which is evicted once its key has received no
+    /// records for `ttl` epochs.
+    ///
+    /// Records are exchanged by `hash` of their key, and applied in epoch order once their epoch
+    /// is complete. The state of a key is created with `Default::default()` for its first record,
+    /// and discarded once epoch `last + ttl` completes, where `last` is the epoch of the key's most
+    /// recent record; a later record for the key starts again from a new state. This suits
+    /// computations like sessionization or enrichment caches, whose state should not be retained
+    /// for keys that are no longer active.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Map, Capture};
+    /// use timely::dataflow::operators::aggregation::StateMap;
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     // records of (key, epoch), counted per key with a time to live of two epochs.
+    ///     vec![('a', 0), ('a', 1), ('b', 1), ('a', 4)]
+    ///         .to_stream(scope)
+    ///         .delay(|x, _time| x.1)
+    ///         .map(|(key, _epoch)| (key, ()))
+    ///         .state_map(2, |key, count: &mut u64, ()| { *count += 1; (*key, *count) }, |_key| 0)
+    ///         .capture()
+    /// });
+    ///
+    /// // the state of 'a' is evicted when epoch three completes.
+    /// assert_eq!(captured.extract(), vec![
+    ///     (0, vec![('a', 1)]),
+    ///     (1, vec![('a', 2), ('b', 1)]),
+    ///     (4, vec![('a', 1)]),
+    /// ]);
+    /// ```
+    fn state_map<
+        R: Data,                                    // output type
+        D: Default+'static,                         // per-key state (data)
+        F: Fn(&K, &mut D, V)->R+'static,            // state update logic
+        H: Fn(&K)->u64+'static,                     // "hash" function for keys
+    >(&self, ttl: u64, fold: F, hash: H) -> Stream<S, R>;
+}
+
+impl<S: Scope<Timestamp=u64>, K: ExchangeData+Hash+Eq, V: ExchangeData> StateMap<S, K, V> for Stream<S, (K, V)> {
+    fn state_map<
+            R: Data,                                    // output type
+            D: Default+'static,                         // per-key state (data)
+            F: Fn(&K, &mut D, V)->R+'static,            // state update logic
+            H: Fn(&K)->u64+'static,                     // "hash" function for keys
+        >(&self, ttl: u64, fold: F, hash: H) -> Stream<S, R> {
+
+        assert!(ttl > 0, "state must live for at least one epoch");
+
+        let mut pending: HashMap<u64, Vec<(K, V)>> = HashMap::new();  // epochs -> records
+        let mut states: HashMap<K, (D, u64)> = HashMap::new();        // keys -> (state, last epoch)
+        let mut expiring: HashMap<u64, Vec<K>> = HashMap::new();      // epochs -> keys to check
+
+        let mut vector = Vec::new();
+
+        self.unary_notify(Exchange::new(move |x: &(K, V)| hash(&x.0)), "StateMap", vec![], move |input, output, notificator| {
+
+            // stash each input and request a notification when ready
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                pending.entry(*time.time()).or_default().append(&mut vector);
+                notificator.notify_at(time.retain());
+            });
+
+            // apply the records of completed epochs in order, and evict states that have expired.
+            notificator.for_each(|time, _, notificator| {
+                let epoch = *time.time();
+                if let Some(pend) = pending.remove(&epoch) {
+                    let expiry = epoch.saturating_add(ttl);
+                    let mut session = output.session(&time);
+                    for (key, val) in pend {
+                        let state = states.entry(key.clone()).or_insert_with(|| (Default::default(), epoch));
+                        state.1 = epoch;
+                        session.give(fold(&key, &mut state.0, val));
+                        expiring.entry(expiry).or_default().push(key);
+                    }
+                    notificator.notify_at(time.delayed(&expiry));
+                }
+                if let Some(keys) = expiring.remove(&epoch) {
+                    for key in keys {
+                        if states.get(&key).map(|state| state.1.saturating_add(ttl) <= epoch) == Some(true) {
+                            states.remove(&key);
+                        }
+                    }
+                }
+            });
+        })
+    }
+}
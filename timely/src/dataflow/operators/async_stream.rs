@@ -0,0 +1,303 @@
+//! Conversions between timely streams and asynchronous streams from the `futures` crate, and
+//! operators driving futures.
+
+use std::pin::Pin;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::collections::VecDeque;
+
+use futures::stream::Stream as AsyncStream;
+use futures::task::{ArcWake, waker_ref};
+
+use crate::Data;
+use crate::progress::Timestamp;
+use crate::scheduling::SyncActivator;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::{Capability, CapabilitySet};
+use crate::dataflow::operators::generic::operator::{source, Operator};
+use crate::dataflow::{Stream, Scope};
+
+/// An item of an asynchronous stream that is introduced into a timely dataflow.
+pub enum Event<T, D> {
+    /// Records at a time, which must be greater or equal to the time of the latest progress.
+    Message(T, Vec<D>),
+    /// Indicates that no further records will be at times not greater or equal to the time, or
+    /// if `None` at any time.
+    Progress(Option<T>),
+}
+
+/// Converts an asynchronous stream to a timely `Stream`.
+pub trait ToStreamAsync<T: Timestamp, D: Data> {
+    /// Converts an asynchronous stream of events to a timely `Stream`.
+    ///
+    /// The stream is polled each time the operator is scheduled, and wakes the operator when it
+    /// is ready to be polled again, even from another thread. The operator holds a capability
+    /// for the time of the latest progress event, and drops it once the stream reports `None`
+    /// progress or ends.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStreamAsync, Capture};
+    /// use timely::dataflow::operators::async_stream::Event;
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let events = vec![
+    ///     Event::Message(0, vec![0, 1]),
+    ///     Event::Progress(Some(1)),
+    ///     Event::Message(1, vec![2]),
+    ///     Event::Message(3, vec![3]),
+    ///     Event::Progress(None),
+    /// ];
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     futures::stream::iter(events)
+    ///         .to_stream_async(scope)
+    ///         .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![0, 1]), (1, vec![2]), (3, vec![3])]);
+    /// ```
+    fn to_stream_async<S: Scope<Timestamp=T>>(self, scope: &mut S) -> Stream<S, D>;
+}
+
+impl<T: Timestamp, D: Data, I: AsyncStream<Item=Event<T, D>>+Unpin+'static> ToStreamAsync<T, D> for I {
+    fn to_stream_async<S: Scope<Timestamp=T>>(self, scope: &mut S) -> Stream<S, D> {
+
+        source(scope, "ToStreamAsync", |capability, info| {
+
+            let activator = Arc::new(scope.sync_activator_for(&info.address[..]));
+
+            let mut capabilities = CapabilitySet::from_elem(capability);
+            let mut stream = Some(self);
+
+            move |output| {
+                let waker = waker_ref(&activator);
+                let mut context = Context::from_waker(&waker);
+                while let Some(Poll::Ready(event)) = stream.as_mut().map(|s| Pin::new(s).poll_next(&mut context)) {
+                    match event {
+                        Some(Event::Message(time, mut data)) => {
+                            output.session(&capabilities.delayed(&time)).give_vec(&mut data);
+                        },
+                        Some(Event::Progress(Some(time))) => {
+                            capabilities.downgrade(&[time]);
+                        },
+                        Some(Event::Progress(None)) | None => {
+                            capabilities.downgrade(&[]);
+                            stream = None;
+                        },
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl ArcWake for SyncActivator {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        // an error indicates that the worker has shut down, and need not be woken.
+        let _ = arc_self.activate();
+    }
+}
+
+/// Converts a timely `Stream` to an asynchronous stream.
+pub trait SinkAsync<T: Timestamp, D: Data> {
+    /// Forwards each batch of records, as a pair of its time and its records, to an asynchronous
+    /// stream that can be consumed outside of timely.
+    ///
+    /// Batches are forwarded as they arrive, and a time may be reported by several batches, in
+    /// any order with respect to other times. The asynchronous stream ends once the input
+    /// frontier is empty, or the dataflow is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures::stream::StreamExt;
+    /// use timely::dataflow::operators::{ToStream, SinkAsync};
+    ///
+    /// let batches = timely::example(|scope| {
+    ///     (0 .. 5).to_stream(scope)
+    ///             .sink_to_async()
+    /// });
+    ///
+    /// let received = futures::executor::block_on(batches.collect::<Vec<_>>());
+    /// assert_eq!(received.into_iter().flat_map(|(_time, data)| data).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+    /// ```
+    fn sink_to_async(&self) -> AsyncReceiver<T, D>;
+}
+
+impl<S: Scope, D: Data> SinkAsync<S::Timestamp, D> for Stream<S, D> {
+    fn sink_to_async(&self) -> AsyncReceiver<S::Timestamp, D> {
+
+        let shared = Arc::new(Mutex::new(Shared {
+            batches: VecDeque::new(),
+            complete: false,
+            waker: None,
+        }));
+
+        let sender = AsyncSender { shared: shared.clone() };
+        let mut vector = Vec::new();
+
+        self.sink(Pipeline, "SinkAsync", move |input| {
+            let mut shared = sender.shared.lock().unwrap();
+            while let Some((time, data)) = input.next() {
+                data.swap(&mut vector);
+                shared.batches.push_back((time.time().clone(), ::std::mem::take(&mut vector)));
+            }
+            if input.frontier().is_empty() {
+                shared.complete = true;
+            }
+            if !shared.batches.is_empty() || shared.complete {
+                if let Some(waker) = shared.waker.take() {
+                    waker.wake();
+                }
+            }
+        });
+
+        AsyncReceiver { shared }
+    }
+}
+
+/// State shared between a sink operator and its asynchronous stream.
+struct Shared<T, D> {
+    batches: VecDeque<(T, Vec<D>)>,
+    complete: bool,
+    waker: Option<Waker>,
+}
+
+/// Marks the asynchronous stream complete when the sink operator is dropped.
+struct AsyncSender<T, D> {
+    shared: Arc<Mutex<Shared<T, D>>>,
+}
+
+impl<T, D> Drop for AsyncSender<T, D> {
+    fn drop(&mut self) {
+        if let Ok(mut shared) = self.shared.lock() {
+            shared.complete = true;
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// An asynchronous stream of the batches of records received by a `sink_to_async` operator.
+pub struct AsyncReceiver<T, D> {
+    shared: Arc<Mutex<Shared<T, D>>>,
+}
+
+impl<T, D> AsyncStream for AsyncReceiver<T, D> {
+    type Item = (T, Vec<D>);
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut shared = self.shared.lock().unwrap();
+        if let Some(batch) = shared.batches.pop_front() {
+            Poll::Ready(Some(batch))
+        }
+        else if shared.complete {
+            Poll::Ready(None)
+        }
+        else {
+            shared.waker = Some(context.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Enriches records with the results of asynchronous lookups.
+pub trait EnrichAsync<G: Scope, D: Data> {
+    /// Pairs each record with the output of the future returned by `lookup` for it.
+    ///
+    /// At most `concurrency` lookups are in flight at a time, and further records wait in arrival
+    /// order for a lookup to complete. The futures are polled by the operator, which is woken
+    /// when any of them can make progress, even by another thread. Enriched records are produced
+    /// as their lookups complete, which need not be in arrival order, but always at the time of
+    /// the record: the operator holds a capability for each record until its lookup completes.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures::channel::oneshot;
+    /// use timely::dataflow::operators::{ToStream, EnrichAsync, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10u64).to_stream(scope)
+    ///               .enrich_async(4, |x| {
+    ///                   // a response computed by another thread, as a remote store might.
+    ///                   let (send, recv) = oneshot::channel();
+    ///                   let x = *x;
+    ///                   std::thread::spawn(move || send.send(x * x).unwrap());
+    ///                   async move { recv.await.unwrap() }
+    ///               })
+    ///               .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, (0..10).map(|x| (x, x * x)).collect())]);
+    /// ```
+    fn enrich_async<E, Fut, F>(&self, concurrency: usize, lookup: F) -> Stream<G, (D, E)>
+    where
+        E: Data,
+        Fut: Future<Output=E>+'static,
+        F: Fn(&D)->Fut+'static;
+}
+
+impl<G: Scope, D: Data> EnrichAsync<G, D> for Stream<G, D> {
+    fn enrich_async<E, Fut, F>(&self, concurrency: usize, lookup: F) -> Stream<G, (D, E)>
+    where
+        E: Data,
+        Fut: Future<Output=E>+'static,
+        F: Fn(&D)->Fut+'static,
+    {
+        assert!(concurrency > 0, "at least one lookup must be allowed in flight");
+
+        self.unary(Pipeline, "EnrichAsync", |_capability, info| {
+
+            let activator = Arc::new(self.scope().sync_activator_for(&info.address[..]));
+
+            // records waiting for a lookup, and records with lookups in flight.
+            let mut waiting = VecDeque::<(Capability<G::Timestamp>, D)>::new();
+            let mut in_flight = Vec::<(Capability<G::Timestamp>, D, Pin<Box<Fut>>)>::new();
+            let mut vector = Vec::new();
+
+            move |input, output| {
+
+                input.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    let capability = time.retain();
+                    waiting.extend(vector.drain(..).map(|datum| (capability.clone(), datum)));
+                });
+
+                let waker = waker_ref(&activator);
+                let mut context = Context::from_waker(&waker);
+
+                // start lookups as slots free up, and produce the records whose lookups complete.
+                loop {
+                    while in_flight.len() < concurrency {
+                        match waiting.pop_front() {
+                            Some((capability, datum)) => {
+                                let future = Box::pin(lookup(&datum));
+                                in_flight.push((capability, datum, future));
+                            },
+                            None => break,
+                        }
+                    }
+
+                    let mut completed = false;
+                    let mut index = 0;
+                    while index < in_flight.len() {
+                        if let Poll::Ready(enriched) = in_flight[index].2.as_mut().poll(&mut context) {
+                            let (capability, datum, _) = in_flight.swap_remove(index);
+                            output.session(&capability).give((datum, enriched));
+                            completed = true;
+                        }
+                        else {
+                            index += 1;
+                        }
+                    }
+
+                    if !completed || waiting.is_empty() {
+                        break;
+                    }
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,163 @@
+//! Injects checkpoint markers at coordinated epochs, and aligns streams on them.
+
+use std::collections::BTreeMap;
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Injects checkpoint markers into `u64`-timestamped streams, and aligns streams on them.
+///
+/// A marker `m` is a record `m` at time `m`, and closes the segment of times less than `m`.
+/// Markers are produced as the input frontier, which all workers share, reaches them, which makes
+/// them a building block for snapshot protocols and for committing to external transactional
+/// systems one segment at a time.
+pub trait Barrier<G: Scope<Timestamp=u64>, D: Data> {
+    /// Produces a marker at a positive multiple of `epochs` once the input frontier reaches it.
+    ///
+    /// The operator holds a capability for its next marker, so that downstream operators can
+    /// tell that no marker will arrive before it. A marker closes all earlier segments, so when
+    /// the frontier passes several multiples at once only the latest is produced. Once the input
+    /// is complete no further markers are produced, and the completion of the marker stream
+    /// closes the remaining segment.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::InputHandle;
+    /// use timely::dataflow::operators::{Input, Probe, Barrier, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    /// use timely::dataflow::ProbeHandle;
+    ///
+    /// let captured = timely::execute_directly(|worker| {
+    ///     let mut input = InputHandle::new();
+    ///     let mut probe = ProbeHandle::new();
+    ///     let captured = worker.dataflow(|scope| {
+    ///         scope.input_from(&mut input)
+    ///              .probe_with(&mut probe)
+    ///              .barrier(4)
+    ///              .capture()
+    ///     });
+    ///     for round in 0 .. 10 {
+    ///         input.send(round);
+    ///         input.advance_to(round + 1);
+    ///         worker.step_while(|| probe.less_than(input.time()));
+    ///     }
+    ///     // a jump in the frontier produces only the latest marker.
+    ///     input.advance_to(1000);
+    ///     worker.step_while(|| probe.less_than(input.time()));
+    ///     captured
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(4, vec![4]), (8, vec![8]), (1000, vec![1000])]);
+    /// ```
+    fn barrier(&self, epochs: u64) -> Stream<G, u64>;
+
+    /// Holds back records until a marker later than their time has arrived.
+    ///
+    /// Records are produced at their own times, released together with the rest of their
+    /// segment once the `markers` input delivers the marker that closes it. Records whose
+    /// segments are already closed pass through, and all held records are released once the
+    /// `markers` input is complete.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::rc::Rc;
+    /// use std::cell::RefCell;
+    /// use timely::dataflow::InputHandle;
+    /// use timely::dataflow::operators::{Input, Barrier, Inspect};
+    ///
+    /// timely::execute_directly(|worker| {
+    ///     let seen = Rc::new(RefCell::new(Vec::new()));
+    ///     let seen2 = seen.clone();
+    ///     let mut input = InputHandle::new();
+    ///     worker.dataflow(|scope| {
+    ///         let data = scope.input_from(&mut input);
+    ///         data.align(&data.barrier(2))
+    ///             .inspect(move |x| seen2.borrow_mut().push(*x));
+    ///     });
+    ///
+    ///     // records at times 0 and 1 wait for the marker at time 2.
+    ///     input.send(0);
+    ///     input.advance_to(1);
+    ///     input.send(1);
+    ///     for _ in 0 .. 10 { worker.step(); }
+    ///     assert!(seen.borrow().is_empty());
+    ///
+    ///     input.advance_to(2);
+    ///     for _ in 0 .. 10 { worker.step(); }
+    ///     assert_eq!(*seen.borrow(), vec![0, 1]);
+    /// });
+    /// ```
+    fn align(&self, markers: &Stream<G, u64>) -> Stream<G, D>;
+}
+
+impl<G: Scope<Timestamp=u64>, D: Data> Barrier<G, D> for Stream<G, D> {
+    fn barrier(&self, epochs: u64) -> Stream<G, u64> {
+
+        assert!(epochs > 0, "markers must be separated by at least one timestamp");
+
+        self.unary_frontier(Pipeline, "Barrier", move |mut capability, _info| {
+            capability.downgrade(&epochs);
+            let mut capability = Some(capability);
+            move |input, output| {
+                input.for_each(|_time, _data| { });
+
+                match input.frontier().frontier().iter().min() {
+                    Some(time) => {
+                        if let Some(cap) = capability.as_mut() {
+                            // the latest marker the frontier has reached closes all earlier segments.
+                            let marker = time - time % epochs;
+                            if marker >= *cap.time() {
+                                output.session(&cap.delayed(&marker)).give(marker);
+                                cap.downgrade(&(marker + epochs));
+                            }
+                        }
+                    },
+                    // an empty frontier has passed every marker, but only the frontiers before it
+                    // say which markers are due, so no more are produced.
+                    None => { capability = None; },
+                }
+            }
+        })
+    }
+
+    fn align(&self, markers: &Stream<G, u64>) -> Stream<G, D> {
+
+        self.binary_frontier(markers, Pipeline, Pipeline, "Align", |_, _| {
+            // the capability and records held for each time, and the latest marker received.
+            let mut held = BTreeMap::new();
+            let mut closed = 0;
+            let mut vector = Vec::new();
+            move |input1, input2, output| {
+                input2.for_each(|_time, data| {
+                    for marker in data.iter() {
+                        closed = ::std::cmp::max(closed, *marker);
+                    }
+                });
+
+                let complete = input2.frontier().is_empty();
+                input1.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    if complete || *time.time() < closed {
+                        output.session(&time).give_vec(&mut vector);
+                    }
+                    else {
+                        held.entry(*time.time())
+                            .or_insert_with(|| (time.retain(), Vec::new()))
+                            .1
+                            .append(&mut vector);
+                    }
+                });
+
+                held.retain(|time, (cap, records)| {
+                    let release = complete || *time < closed;
+                    if release {
+                        output.session(cap).give_vec(records);
+                    }
+                    !release
+                });
+            }
+        })
+    }
+}
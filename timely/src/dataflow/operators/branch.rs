@@ -28,6 +28,24 @@ pub trait Branch<S: Scope, D: Data> {
     ///     odd.inspect(|x| println!("odd numbers: {:?}", x));
     /// });
     /// ```
+    ///
+    /// Both streams are outputs of a single operator, and each record is sent to exactly one.
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Branch, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (odd, even) = timely::example(|scope| {
+    ///     let (odd, even) = (0..10)
+    ///         .to_stream(scope)
+    ///         .branch(|_time, x| *x % 2 == 0);
+    ///
+    ///     (odd.capture(), even.capture())
+    /// });
+    ///
+    /// assert_eq!(odd.extract(), vec![(0, vec![1, 3, 5, 7, 9])]);
+    /// assert_eq!(even.extract(), vec![(0, vec![0, 2, 4, 6, 8])]);
+    /// ```
     fn branch(
         &self,
         condition: impl Fn(&S::Timestamp, &D) -> bool + 'static,
@@ -130,3 +148,119 @@ impl<S: Scope, D: Data> BranchWhen<S, D> for Stream<S, D> {
         (stream1, stream2)
     }
 }
+
+/// Extension trait for streams of `Result`s.
+pub trait OkErr<S: Scope, A: Data, E: Data> {
+    /// Splits a stream of results into a stream of `Ok` values and a stream of `Err` values.
+    ///
+    /// Both streams are outputs of a single operator, which moves each value to its stream.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, OkErr, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (ok, err) = timely::example(|scope| {
+    ///     let (ok, err) = (0..10)
+    ///         .to_stream(scope)
+    ///         .map(|x| if x % 3 == 0 { Err(format!("{} is a multiple of three", x)) } else { Ok(x) })
+    ///         .ok_err();
+    ///
+    ///     (ok.capture(), err.capture())
+    /// });
+    ///
+    /// assert_eq!(ok.extract(), vec![(0, vec![1, 2, 4, 5, 7, 8])]);
+    /// assert_eq!(err.extract()[0].1.len(), 4);
+    /// ```
+    fn ok_err(&self) -> (Stream<S, A>, Stream<S, E>);
+}
+
+impl<S: Scope, A: Data, E: Data> OkErr<S, A, E> for Stream<S, Result<A, E>> {
+    fn ok_err(&self) -> (Stream<S, A>, Stream<S, E>) {
+        let mut builder = OperatorBuilder::new("OkErr".to_owned(), self.scope());
+
+        let mut input = builder.new_input(self, Pipeline);
+        let (mut output_ok, stream_ok) = builder.new_output();
+        let (mut output_err, stream_err) = builder.new_output();
+
+        builder.build(move |_| {
+            let mut vector = Vec::new();
+            move |_frontiers| {
+                let mut ok_handle = output_ok.activate();
+                let mut err_handle = output_err.activate();
+
+                input.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    let mut ok = ok_handle.session(&time);
+                    let mut err = err_handle.session(&time);
+                    for datum in vector.drain(..) {
+                        match datum {
+                            Ok(datum) => ok.give(datum),
+                            Err(datum) => err.give(datum),
+                        }
+                    }
+                });
+            }
+        });
+
+        (stream_ok, stream_err)
+    }
+}
+
+/// Separates records whose event times are too far behind the stream's timestamps.
+///
+/// Records ingested from external systems often carry their own event times, and are introduced
+/// at timestamps reflecting when they arrived. A record is late if its event time plus the
+/// allowed lateness is less than its timestamp; such a record arrives after operators like
+/// windows may have closed the times at which it belongs, and cannot be delayed to its event
+/// time. Records that are not late keep their timestamps.
+pub trait Late<S: Scope<Timestamp=u64>, D: Data> {
+    /// Splits a stream into records that are on time and records that are late.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Late, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (on_time, late) = timely::example(|scope| {
+    ///     // records of (event time, value) introduced at time 10.
+    ///     let (on_time, late) = vec![(9, 'a'), (4, 'b'), (7, 'c')]
+    ///         .to_stream(scope)
+    ///         .delay(|_, _time| 10)
+    ///         .split_late(3, |x| x.0);
+    ///
+    ///     (on_time.capture(), late.capture())
+    /// });
+    ///
+    /// assert_eq!(on_time.extract(), vec![(10, vec![(7, 'c'), (9, 'a')])]);
+    /// assert_eq!(late.extract(), vec![(10, vec![(4, 'b')])]);
+    /// ```
+    fn split_late(&self, allowed_lateness: u64, event_time: impl Fn(&D)->u64+'static) -> (Stream<S, D>, Stream<S, D>);
+
+    /// Discards records that are late, and keeps those that are on time.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Late, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     vec![(9, 'a'), (4, 'b'), (7, 'c')]
+    ///         .to_stream(scope)
+    ///         .delay(|_, _time| 10)
+    ///         .drop_late(3, |x| x.0)
+    ///         .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(10, vec![(7, 'c'), (9, 'a')])]);
+    /// ```
+    fn drop_late(&self, allowed_lateness: u64, event_time: impl Fn(&D)->u64+'static) -> Stream<S, D> {
+        self.split_late(allowed_lateness, event_time).0
+    }
+}
+
+impl<S: Scope<Timestamp=u64>, D: Data> Late<S, D> for Stream<S, D> {
+    fn split_late(&self, allowed_lateness: u64, event_time: impl Fn(&D)->u64+'static) -> (Stream<S, D>, Stream<S, D>) {
+        self.branch(move |time, datum| event_time(datum).saturating_add(allowed_lateness) < *time)
+    }
+}
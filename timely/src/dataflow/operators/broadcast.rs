@@ -8,6 +8,10 @@ use crate::dataflow::operators::{Map, Exchange};
 pub trait Broadcast<D: ExchangeData> {
     /// Broadcast records to all workers.
     ///
+    /// Each record is replicated once for each worker before it is exchanged, so that the
+    /// replicas are counted as produced messages and downstream frontiers and notifications
+    /// account for every copy.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::operators::{ToStream, Broadcast, Inspect};
@@ -18,15 +22,42 @@ pub trait Broadcast<D: ExchangeData> {
     ///            .inspect(|x| println!("seen: {:?}", x));
     /// });
     /// ```
+    ///
+    /// Every worker receives every record, and downstream notifications are only delivered
+    /// once all copies have arrived.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Broadcast, Accumulate, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (send, recv) = ::std::sync::mpsc::channel();
+    /// let send = Arc::new(Mutex::new(send));
+    ///
+    /// timely::execute(timely::Configuration::Process(3), move |worker| {
+    ///     let send = send.lock().unwrap().clone();
+    ///     let index = worker.index();
+    ///     worker.dataflow::<u64,_,_>(move |scope| {
+    ///         (0..10u64).filter(move |x| x % 3 == index as u64)
+    ///                   .to_stream(scope)
+    ///                   .broadcast()
+    ///                   .count()
+    ///                   .capture_into(send);
+    ///     });
+    /// }).unwrap();
+    ///
+    /// // each of the three workers observes all ten records.
+    /// assert_eq!(recv.extract(), vec![(0, vec![10, 10, 10])]);
+    /// ```
     fn broadcast(&self) -> Self;
 }
 
 impl<G: Scope, D: ExchangeData> Broadcast<D> for Stream<G, D> {
     fn broadcast(&self) -> Stream<G, D> {
 
-        // NOTE: Simplified implementation due to underlying motion
-        // in timely dataflow internals. Optimize once they have
-        // settled down.
+        // NOTE: Replication must happen upstream of the exchange, rather than in a
+        // parallelization contract, as progress tracking counts produced messages
+        // before they reach the pact and requires that pacts preserve record counts.
         let peers = self.scope().peers() as u64;
         self.flat_map(move |x| (0 .. peers).map(move |i| (i,x.clone())))
             .exchange(|ix| ix.0)
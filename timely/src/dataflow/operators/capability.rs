@@ -354,6 +354,34 @@ impl<T: Timestamp> CapabilitySet<T> {
     }
 
     /// Inserts `capability` into the set, discarding redundant capabilities.
+    ///
+    /// A capability is redundant if another in the set has a time less or equal to its time, so
+    /// the times of the set always form an antichain.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, CapabilitySet};
+    /// use timely::dataflow::operators::generic::Operator;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10u64).to_stream(scope)
+    ///         .unary_frontier::<(),_,_,_>(Pipeline, "example", |default_cap, _info| {
+    ///             let mut caps = CapabilitySet::new();
+    ///             caps.insert(default_cap.delayed(&2));
+    ///             caps.insert(default_cap.delayed(&1));
+    ///             caps.insert(default_cap.delayed(&3));
+    ///             assert_eq!(caps.iter().map(|cap| *cap.time()).collect::<Vec<_>>(), vec![1]);
+    ///             move |input, _output| {
+    ///                 input.for_each(|_time, _data| { });
+    ///                 // release the capabilities once the input is complete.
+    ///                 if input.frontier().is_empty() {
+    ///                     caps.downgrade(&[]);
+    ///                 }
+    ///             }
+    ///         });
+    /// });
+    /// ```
     pub fn insert(&mut self, capability: Capability<T>) {
         if !self.elements.iter().any(|c| c.less_equal(&capability)) {
             self.elements.retain(|c| !capability.less_equal(c));
@@ -370,14 +398,15 @@ impl<T: Timestamp> CapabilitySet<T> {
 
     /// Downgrades the set of capabilities to correspond with the times in `frontier`.
     ///
+    /// Redundant times in `frontier` are discarded, as by `insert`.
+    ///
     /// This method panics if any element of `frontier` is not greater or equal to some element of `self.elements`.
     pub fn downgrade(&mut self, frontier: &[T]) {
-        let count = self.elements.len();
+        let mut downgraded = CapabilitySet::new();
         for time in frontier.iter() {
-            let capability = self.delayed(time);
-            self.elements.push(capability);
+            downgraded.insert(self.delayed(time));
         }
-        self.elements.drain(..count);
+        *self = downgraded;
     }
 }
 
@@ -3,7 +3,7 @@
 //! All timely dataflow streams can be captured, but there are many ways to capture
 //! these streams. A stream may be `capture_into`'d any type implementing `EventPusher`,
 //! and there are several default implementations, including a linked-list, Rust's MPSC
-//! queue, and a binary serializer wrapping any `W: Write`.
+//! queue, a shared `Rc<RefCell<Vec<_>>>`, and a binary serializer wrapping any `W: Write`.
 
 use crate::Data;
 use crate::dataflow::{Scope, Stream};
@@ -41,6 +41,13 @@ impl<T, D> EventPusher<T, D> for ::std::sync::mpsc::Sender<Event<T, D>> {
     }
 }
 
+// implementation for a shared vector, which collects all events.
+impl<T, D> EventPusher<T, D> for ::std::rc::Rc<::std::cell::RefCell<Vec<Event<T, D>>>> {
+    fn push(&mut self, event: Event<T, D>) {
+        self.borrow_mut().push(event);
+    }
+}
+
 /// A linked-list event pusher and iterator.
 pub mod link {
 
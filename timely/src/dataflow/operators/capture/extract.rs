@@ -6,8 +6,9 @@ use super::Event;
 pub trait Extract<T: Ord, D: Ord> {
     /// Converts `self` into a sequence of timestamped data.
     ///
-    /// Currently this is only implemented for `Receiver<Event<T, D>>`, and is used only
-    /// to easily pull data out of a timely dataflow computation once it has completed.
+    /// This is implemented for `Receiver<Event<T, D>>` and `Vec<Event<T, D>>`, and is used only
+    /// to easily pull data out of a timely dataflow computation once it has completed. The result
+    /// is sorted by time, with the data for each time collected together and sorted.
     ///
     /// # Examples
     ///
@@ -48,6 +49,39 @@ pub trait Extract<T: Ord, D: Ord> {
 }
 
 impl<T: Ord, D: Ord> Extract<T,D> for ::std::sync::mpsc::Receiver<Event<T, D>> {
+    fn extract(self) -> Vec<(T, Vec<D>)> {
+        self.iter().collect::<Vec<_>>().extract()
+    }
+}
+
+impl<T: Ord, D: Ord> Extract<T,D> for Vec<Event<T, D>> {
+    /// Extracts timestamped data from events collected into a vector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    /// use std::cell::RefCell;
+    /// use timely::dataflow::operators::{Capture, ToStream, Delay};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// timely::execute(timely::Configuration::Thread, |worker| {
+    ///
+    ///     let events = Rc::new(RefCell::new(Vec::new()));
+    ///
+    ///     worker.dataflow::<u64,_,_>(|scope|
+    ///         (0..10).to_stream(scope)
+    ///                .delay(|x, _time| x % 2)
+    ///                .capture_into(events.clone())
+    ///     );
+    ///
+    ///     // run the dataflow to completion.
+    ///     while worker.step() { }
+    ///
+    ///     let extracted = events.replace(Vec::new()).extract();
+    ///     assert_eq!(extracted, vec![(0, vec![0, 2, 4, 6, 8]), (1, vec![1, 3, 5, 7, 9])]);
+    /// }).unwrap();
+    /// ```
     fn extract(self) -> Vec<(T, Vec<D>)> {
         let mut result = Vec::new();
         for event in self {
@@ -72,6 +72,40 @@
 //!     })
 //! }).unwrap();
 //! ```
+//!
+//! Captured streams need not be replayed by the same computation. Here one computation captures
+//! its output to a file, and a later computation replays the file, recovering the data at each
+//! timestamp and the progress of the original stream. This is one way to compare a computation's
+//! output against known good output, or to reprocess it offline.
+//!
+//! ```
+//! use std::fs::File;
+//! use timely::dataflow::operators::{Capture, ToStream, Delay};
+//! use timely::dataflow::operators::capture::{EventReader, EventWriter, Extract, Replay};
+//!
+//! let path = std::env::temp_dir().join("timely-capture-replay-example");
+//!
+//! let file = File::create(&path).unwrap();
+//! timely::execute(timely::Configuration::Thread, move |worker| {
+//!     let file = file.try_clone().unwrap();
+//!     worker.dataflow::<u64,_,_>(|scope|
+//!         (0..10u64)
+//!             .to_stream(scope)
+//!             .delay(|x, _time| x / 4)
+//!             .capture_into(EventWriter::new(file))
+//!     );
+//! }).unwrap();
+//!
+//! let file = File::open(&path).unwrap();
+//! let captured = timely::example(move |scope| {
+//!     Some(EventReader::<_,u64,_>::new(file))
+//!         .replay_into(scope)
+//!         .capture()
+//! });
+//!
+//! assert_eq!(captured.extract(), vec![(0, vec![0, 1, 2, 3]), (1, vec![4, 5, 6, 7]), (2, vec![8, 9])]);
+//! std::fs::remove_file(&path).unwrap();
+//! ```
 
 pub use self::capture::Capture;
 pub use self::replay::Replay;
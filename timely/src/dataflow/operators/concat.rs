@@ -33,6 +33,11 @@ impl<G: Scope, D: Data> Concat<G, D> for Stream<G, D> {
 pub trait Concatenate<G: Scope, D: Data> {
     /// Merge the contents of multiple streams.
     ///
+    /// The streams are merged by a single operator with one input for each stream, rather than
+    /// by a chain of `concat` operators, so merging many streams adds only one operator to the
+    /// dataflow graph. Prefer this to repeated calls to `concat` when there are more than two
+    /// streams. Called on a stream, the stream is merged along with `sources`.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::operators::{ToStream, Concatenate, Inspect};
@@ -0,0 +1,121 @@
+//! Applies the latest configuration from a control stream to the records of a data stream.
+
+use std::collections::BTreeMap;
+
+use crate::{Data, ExchangeData};
+use crate::order::TotalOrder;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::Broadcast;
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Applies the latest configuration from a control stream to the records of a data stream.
+pub trait WithControl<G: Scope, D: Data> where G::Timestamp: TotalOrder {
+    /// Applies `logic` to each record, with the latest control record at or before its time.
+    ///
+    /// The control stream is broadcast to all workers. Records of the data stream are held until
+    /// the control stream's frontier has passed their time, so that every control record that
+    /// applies to them has arrived, and then `logic` is called with the control record of the
+    /// greatest time not greater than theirs, or `None` if there is none. Among several control
+    /// records at the same time, the greatest applies, so that all workers make the same choice
+    /// whatever order the records arrive in. This is the usual way to reconfigure an operator
+    /// while it runs, with each record seeing a configuration determined by its time.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, WithControl, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     // a factor of one from time zero, and of ten from time three.
+    ///     let factors = vec![(0, 1), (3, 10)].to_stream(scope)
+    ///                                        .delay(|x, _time| x.0);
+    ///     (0..6u64).to_stream(scope)
+    ///              .delay(|x, _time| *x)
+    ///              .with_control(&factors, |factor, x| x * factor.map(|f| f.1).unwrap_or(0))
+    ///              .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![
+    ///     (0, vec![0]), (1, vec![1]), (2, vec![2]), (3, vec![30]), (4, vec![40]), (5, vec![50]),
+    /// ]);
+    /// ```
+    ///
+    /// Control records at the same time are resolved by taking the greatest.
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, WithControl, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     let factors = vec![3, 10, 2].to_stream(scope);
+    ///     (0..3u64).to_stream(scope)
+    ///              .with_control(&factors, |factor, x| x * factor.cloned().unwrap_or(0))
+    ///              .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![0, 10, 20])]);
+    /// ```
+    fn with_control<C, R, F>(&self, control: &Stream<G, C>, logic: F) -> Stream<G, R>
+    where
+        C: ExchangeData+Ord,
+        R: Data,
+        F: FnMut(Option<&C>, D)->R+'static;
+}
+
+impl<G: Scope, D: Data> WithControl<G, D> for Stream<G, D> where G::Timestamp: TotalOrder {
+    fn with_control<C, R, F>(&self, control: &Stream<G, C>, mut logic: F) -> Stream<G, R>
+    where
+        C: ExchangeData+Ord,
+        R: Data,
+        F: FnMut(Option<&C>, D)->R+'static,
+    {
+        self.binary_frontier(&control.broadcast(), Pipeline, Pipeline, "WithControl", |_, _| {
+
+            // the greatest control record at each time, and data records awaiting their control.
+            let mut controls = BTreeMap::new();
+            let mut stash = BTreeMap::new();
+            let mut vector = Vec::new();
+
+            move |input1, input2, output| {
+
+                input2.for_each(|time, data| {
+                    if let Some(greatest) = data.iter().max() {
+                        let control = controls.entry(time.time().clone()).or_insert_with(|| greatest.clone());
+                        if *control < *greatest {
+                            *control = greatest.clone();
+                        }
+                    }
+                });
+
+                input1.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    stash.entry(time.time().clone())
+                         .or_insert_with(|| (time.retain(), Vec::new()))
+                         .1
+                         .append(&mut vector);
+                });
+
+                // apply controls to records at times the control frontier has passed.
+                while let Some(time) = stash.keys().next().cloned().filter(|t| !input2.frontier().less_equal(t)) {
+                    let (capability, records) = stash.remove(&time).unwrap();
+                    let control = controls.range(..= time).next_back().map(|(_, control)| control);
+                    let mut session = output.session(&capability);
+                    for record in records {
+                        session.give(logic(control, record));
+                    }
+                }
+
+                // discard controls superseded before any time data may yet arrive at.
+                match input1.frontier().frontier().iter().chain(stash.keys()).min().cloned() {
+                    Some(earliest) => {
+                        if let Some(keep) = controls.range(..= earliest).next_back().map(|(time, _)| time.clone()) {
+                            controls = controls.split_off(&keep);
+                        }
+                    },
+                    None => controls.clear(),
+                }
+            }
+        })
+    }
+}
@@ -45,6 +45,23 @@ pub trait Accumulate<G: Scope, D: Data> {
     /// let extracted = captured.extract();
     /// assert_eq!(extracted, vec![(0, vec![10])]);
     /// ```
+    ///
+    /// Each time is counted separately, and its count is produced only once the time is complete.
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Accumulate, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .delay(|x, _time| if *x < 3 { 0 } else { 1 })
+    ///            .count()
+    ///            .capture()
+    /// });
+    ///
+    /// let extracted = captured.extract();
+    /// assert_eq!(extracted, vec![(0, vec![3]), (1, vec![7])]);
+    /// ```
     fn count(&self) -> Stream<G, usize> {
         self.accumulate(0, |sum, data| *sum += data.len())
     }
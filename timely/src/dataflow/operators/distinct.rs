@@ -0,0 +1,182 @@
+//! Removes duplicate records from a stream.
+use std::hash::{Hash, Hasher};
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+
+use crate::ExchangeData;
+use crate::order::TotalOrder;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::channels::pact::Exchange;
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::operators::Map;
+use crate::dataflow::operators::sketch::{sketch_with, HyperLogLog};
+
+/// Removes duplicate records from a stream.
+pub trait Distinct<G: Scope, D: ExchangeData+Hash+Eq> {
+    /// Produces each distinct record once for each time at which it occurs.
+    ///
+    /// Records are exchanged by their hash, and the first occurrence of each record at
+    /// each time is produced immediately. The records seen at a time are discarded once
+    /// that time is complete.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Distinct, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     vec![0, 1, 2, 2, 2, 3, 3, 4].into_iter()
+    ///         .to_stream(scope)
+    ///         .distinct()
+    ///         .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![0, 1, 2, 3, 4])]);
+    /// ```
+    ///
+    /// Duplicates are removed across all workers.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Distinct, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (send, recv) = ::std::sync::mpsc::channel();
+    /// let send = Arc::new(Mutex::new(send));
+    ///
+    /// timely::execute(timely::Configuration::Process(2), move |worker| {
+    ///     let send = send.lock().unwrap().clone();
+    ///     let data = if worker.index() == 0 { vec![0, 1, 2, 2, 2, 3, 3, 4] }
+    ///                else                   { vec![0, 0, 3, 4, 4, 5, 7, 7] };
+    ///     worker.dataflow::<u64,_,_>(move |scope| {
+    ///         data.to_stream(scope)
+    ///             .distinct()
+    ///             .capture_into(send);
+    ///     });
+    /// }).unwrap();
+    ///
+    /// assert_eq!(recv.extract(), vec![(0, vec![0, 1, 2, 3, 4, 5, 7])]);
+    /// ```
+    fn distinct(&self) -> Stream<G, D>;
+
+    /// Produces each distinct record once, at the first time at which it occurs.
+    ///
+    /// Unlike `distinct`, records are retained across times, and a record is not produced
+    /// again at later times. Records are produced once their time is complete, so that each
+    /// is reported at its least time.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Map, Distinct, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     // times 0, 1, and 2 hold records [0, 1, 2], [3, 0, 1], and [2, 3, 0].
+    ///     (0..9).to_stream(scope)
+    ///           .delay(|x, _time| x / 3)
+    ///           .map(|x| x % 4)
+    ///           .distinct_total()
+    ///           .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![0, 1, 2]), (1, vec![3])]);
+    /// ```
+    fn distinct_total(&self) -> Stream<G, D> where G::Timestamp: TotalOrder;
+
+    /// Estimates the number of distinct records at each time, produced at worker zero once the
+    /// time is complete.
+    ///
+    /// Each worker builds a `HyperLogLog` sketch with `2^precision` registers from its records at
+    /// a time, and worker zero merges the sketches and reports the estimate. Unlike `distinct`,
+    /// records are not exchanged, and the memory used does not grow with the number of distinct
+    /// records. The estimate has a relative standard error of about `1.04 / sqrt(2^precision)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Distinct, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..100_000u64).to_stream(scope)
+    ///                    .map(|x| x % 10_000)
+    ///                    .distinct_count_approx(12)
+    ///                    .capture()
+    /// });
+    ///
+    /// let estimate = captured.extract()[0].1[0] as f64;
+    /// assert!((estimate - 10_000.0).abs() < 500.0);
+    /// ```
+    fn distinct_count_approx(&self, precision: u32) -> Stream<G, u64>;
+}
+
+impl<G: Scope, D: ExchangeData+Hash+Eq> Distinct<G, D> for Stream<G, D> {
+
+    fn distinct(&self) -> Stream<G, D> {
+
+        let mut seen_by_time = HashMap::new();
+        let mut vector = Vec::new();
+
+        self.unary_notify(Exchange::new(hash), "Distinct", vec![], move |input, output, notificator| {
+
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                let seen = seen_by_time.entry(time.time().clone()).or_insert_with(HashSet::new);
+                let mut session = output.session(&time);
+                for datum in vector.drain(..) {
+                    if !seen.contains(&datum) {
+                        seen.insert(datum.clone());
+                        session.give(datum);
+                    }
+                }
+                notificator.notify_at(time.retain());
+            });
+
+            // discard records seen at completed times.
+            notificator.for_each(|time, _, _| {
+                seen_by_time.remove(time.time());
+            });
+        })
+    }
+
+    fn distinct_total(&self) -> Stream<G, D> where G::Timestamp: TotalOrder {
+
+        let mut seen = HashSet::new();
+        let mut stash = HashMap::new();
+        let mut vector = Vec::new();
+
+        self.unary_notify(Exchange::new(hash), "DistinctTotal", vec![], move |input, output, notificator| {
+
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                stash.entry(time.time().clone())
+                     .or_insert_with(Vec::new)
+                     .append(&mut vector);
+                notificator.notify_at(time.retain());
+            });
+
+            // completed times are delivered in order, so earlier occurrences are seen first.
+            notificator.for_each(|time, _, _| {
+                if let Some(data) = stash.remove(time.time()) {
+                    let mut session = output.session(&time);
+                    for datum in data {
+                        if !seen.contains(&datum) {
+                            seen.insert(datum.clone());
+                            session.give(datum);
+                        }
+                    }
+                }
+            });
+        })
+    }
+
+    fn distinct_count_approx(&self, precision: u32) -> Stream<G, u64> {
+        sketch_with(self, HyperLogLog::new(precision), |sketch, datum| sketch.insert_hash(hash(datum)))
+            .map(|sketch| sketch.estimate())
+    }
+}
+
+fn hash<D: Hash>(datum: &D) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    datum.hash(&mut hasher);
+    hasher.finish()
+}
@@ -60,15 +60,21 @@ use crate::dataflow::scopes::child::Iterative;
 pub trait EnterAt<G: Scope, T: Timestamp, D: Data> {
     /// Moves the `Stream` argument into a child of its current `Scope` setting the timestamp for each element by `initial`.
     ///
+    /// Each record keeps its outer timestamp, and starts at the loop counter `initial` computes
+    /// for it rather than at zero. This allows records to join an iterative computation at
+    /// different rounds, for example to prioritize some records over others.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::scopes::Scope;
-    /// use timely::dataflow::operators::{EnterAt, Leave, ToStream};
+    /// use timely::dataflow::operators::{EnterAt, Leave, ToStream, Inspect};
     ///
     /// timely::example(|outer| {
     ///     let stream = (0..9u64).to_stream(outer);
     ///     let output = outer.iterative(|inner| {
-    ///         stream.enter_at(inner, |x| *x).leave()
+    ///         stream.enter_at(inner, |x| *x)
+    ///               .inspect_time(|time, x| assert_eq!(time.inner, *x))
+    ///               .leave()
     ///     });
     /// });
     /// ```
@@ -1,7 +1,8 @@
 //! Exchange records between workers.
 
 use crate::ExchangeData;
-use crate::dataflow::channels::pact::Exchange as ExchangePact;
+use crate::Data;
+use crate::dataflow::channels::pact::{Pipeline, Exchange as ExchangePact, KeyHash};
 use crate::dataflow::{Stream, Scope};
 use crate::dataflow::operators::generic::operator::Operator;
 
@@ -23,6 +24,31 @@ pub trait Exchange<T, D: ExchangeData> {
     /// });
     /// ```
     fn exchange(&self, route: impl Fn(&D)->u64+'static) -> Self;
+
+    /// Exchange records between workers by the key `K`, unless they are already partitioned by it.
+    ///
+    /// The resulting stream is recorded as partitioned by `K`, so that later exchanges by the
+    /// same key, with `exchange_by` or the `KeyedPipeline` contract, leave records in place.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+    /// use timely::dataflow::channels::pact::KeyHash;
+    ///
+    /// struct Value;
+    /// impl KeyHash<u64> for Value {
+    ///     fn hash(x: &u64) -> u64 { *x }
+    /// }
+    ///
+    /// timely::example(|scope| {
+    ///     let keyed = (0..10u64).to_stream(scope)
+    ///                           .exchange_by::<Value>();
+    ///     assert!(keyed.is_partitioned_by::<Value>());
+    ///     // a second exchange by the same key adds no operator.
+    ///     assert_eq!(keyed.exchange_by::<Value>().name(), keyed.name());
+    /// });
+    /// ```
+    fn exchange_by<K: KeyHash<D>>(&self) -> Self;
 }
 
 // impl<T: Timestamp, G: Scope<Timestamp=T>, D: ExchangeData> Exchange<T, D> for Stream<G, D> {
@@ -36,4 +62,47 @@ impl<G: Scope, D: ExchangeData> Exchange<G::Timestamp, D> for Stream<G, D> {
             });
         })
     }
+
+    fn exchange_by<K: KeyHash<D>>(&self) -> Stream<G, D> {
+        if self.is_partitioned_by::<K>() {
+            self.clone()
+        }
+        else {
+            self.exchange(|datum| K::hash(datum))
+                .with_partitioning::<K>()
+        }
+    }
+}
+
+/// Pairs records with precomputed routing keys.
+pub trait PreHashed<G: Scope, D: Data> {
+    /// Pairs each record with the result of `hash`, for exchange by the `ExchangeByKey` contract.
+    ///
+    /// The hash is evaluated once for each record, however many times the pairs are exchanged.
+    /// This avoids repeatedly hashing large composite keys in pipelines that shuffle the same
+    /// records several times.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, PreHashed, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10u64).to_stream(scope)
+    ///               .pre_hashed(|x| x / 2)
+    ///               .inspect(|(key, x)| assert_eq!(*key, x / 2));
+    /// });
+    /// ```
+    fn pre_hashed(&self, hash: impl Fn(&D)->u64+'static) -> Stream<G, (u64, D)>;
+}
+
+impl<G: Scope, D: Data> PreHashed<G, D> for Stream<G, D> {
+    fn pre_hashed(&self, hash: impl Fn(&D)->u64+'static) -> Stream<G, (u64, D)> {
+        let mut vector = Vec::new();
+        self.unary(Pipeline, "PreHashed", move |_,_| move |input, output| {
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                output.session(&time).give_iterator(vector.drain(..).map(|x| (hash(&x), x)));
+            });
+        })
+    }
 }
@@ -4,7 +4,7 @@ use crate::Data;
 
 use crate::progress::{Timestamp, PathSummary};
 use crate::progress::frontier::Antichain;
-use crate::order::Product;
+use crate::order::{Product, PartialOrder};
 
 use crate::dataflow::channels::pushers::Tee;
 use crate::dataflow::channels::pact::Pipeline;
@@ -12,6 +12,7 @@ use crate::dataflow::{Stream, Scope};
 use crate::dataflow::scopes::child::Iterative;
 use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
 use crate::dataflow::operators::generic::OutputWrapper;
+use crate::dataflow::operators::{Enter, Leave, Concat};
 
 /// Creates a `Stream` and a `Handle` to later bind the source of that `Stream`.
 pub trait Feedback<G: Scope> {
@@ -19,7 +20,7 @@ pub trait Feedback<G: Scope> {
     ///
     /// The resulting `Stream` will have its data defined by a future call to `connect_loop` with
     /// its `Handle` passed as an argument. Data passed through the stream will have their
-    /// timestamps advanced by `summary`, and will be dropped if the result exceeds `limit`.
+    /// timestamps advanced by `summary`.
     ///
     /// # Examples
     /// ```
@@ -37,15 +38,41 @@ pub trait Feedback<G: Scope> {
     /// });
     /// ```
     fn feedback<D: Data>(&mut self, summary: <G::Timestamp as Timestamp>::Summary) -> (Handle<G, D>, Stream<G, D>);
+
+    /// Creates a `Stream` and a `Handle` to later bind the source of that `Stream`, dropping data
+    /// whose advanced timestamps are not less or equal to `limit`.
+    ///
+    /// This ensures that data circulate a bounded number of times.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::Scope;
+    /// use timely::dataflow::operators::{Feedback, ConnectLoop, ToStream, Concat, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     // circulate 0..3 until time 2.
+    ///     let (handle, cycle) = scope.feedback_bounded(1, 2);
+    ///     let stream = (0..3).to_stream(scope).concat(&cycle);
+    ///     stream.connect_loop(handle);
+    ///     stream.capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![0, 1, 2]), (1, vec![0, 1, 2]), (2, vec![0, 1, 2])]);
+    /// ```
+    fn feedback_bounded<D: Data>(&mut self, summary: <G::Timestamp as Timestamp>::Summary, limit: G::Timestamp) -> (Handle<G, D>, Stream<G, D>);
 }
 
+/// The `Handle` and `Stream` of a loop variable in an iterative scope.
+pub type LoopVariableParts<'a, G, T, D> = (Handle<Iterative<'a, G, T>, D>, Stream<Iterative<'a, G, T>, D>);
+
 /// Creates a `Stream` and a `Handle` to later bind the source of that `Stream`.
 pub trait LoopVariable<'a, G: Scope, T: Timestamp> {
     /// Creates a `Stream` and a `Handle` to later bind the source of that `Stream`.
     ///
     /// The resulting `Stream` will have its data defined by a future call to `connect_loop` with
     /// its `Handle` passed as an argument. Data passed through the stream will have their
-    /// timestamps advanced by `summary`, and will be dropped if the result exceeds `limit`.
+    /// timestamps advanced by `summary`.
     ///
     /// # Examples
     /// ```
@@ -64,7 +91,33 @@ pub trait LoopVariable<'a, G: Scope, T: Timestamp> {
     ///     });
     /// });
     /// ```
-    fn loop_variable<D: Data>(&mut self, summary: T::Summary) -> (Handle<Iterative<'a, G, T>, D>, Stream<Iterative<'a, G, T>, D>);
+    fn loop_variable<D: Data>(&mut self, summary: T::Summary) -> LoopVariableParts<'a, G, T, D>;
+
+    /// Creates a `Stream` and a `Handle` to later bind the source of that `Stream`, dropping data
+    /// whose advanced loop counters are not less or equal to `limit`.
+    ///
+    /// This ensures that the loop completes, without a separate operator to remove data.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::Scope;
+    /// use timely::dataflow::operators::{LoopVariable, ConnectLoop, ToStream, Concat, Map, Leave, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     // double 1 in each of at most ten iterations.
+    ///     scope.iterative::<u32,_,_>(|inner| {
+    ///         let (handle, cycle) = inner.loop_variable_bounded(1, 10);
+    ///         let stream = vec![1u64].to_stream(inner).concat(&cycle).map(|x| 2 * x);
+    ///         stream.connect_loop(handle);
+    ///         stream.leave()
+    ///     })
+    ///     .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract()[0].1.last(), Some(&2048));
+    /// ```
+    fn loop_variable_bounded<D: Data>(&mut self, summary: T::Summary, limit: T) -> LoopVariableParts<'a, G, T, D>;
 }
 
 impl<G: Scope> Feedback<G> for G {
@@ -73,14 +126,70 @@ impl<G: Scope> Feedback<G> for G {
         let mut builder = OperatorBuilder::new("Feedback".to_owned(), self.clone());
         let (output, stream) = builder.new_output();
 
-        (Handle { builder, summary, output }, stream)
+        (Handle { builder, summary, output, limit: None }, stream)
+    }
+
+    fn feedback_bounded<D: Data>(&mut self, summary: <G::Timestamp as Timestamp>::Summary, limit: G::Timestamp) -> (Handle<G, D>, Stream<G, D>) {
+        let (mut handle, stream) = self.feedback(summary);
+        handle.limit = Some(Box::new(move |time| time.less_equal(&limit)));
+        (handle, stream)
     }
 }
 
 impl<'a, G: Scope, T: Timestamp> LoopVariable<'a, G, T> for Iterative<'a, G, T> {
-    fn loop_variable<D: Data>(&mut self, summary: T::Summary) -> (Handle<Iterative<'a, G, T>, D>, Stream<Iterative<'a, G, T>, D>) {
+    fn loop_variable<D: Data>(&mut self, summary: T::Summary) -> LoopVariableParts<'a, G, T, D> {
         self.feedback(Product::new(Default::default(), summary))
     }
+
+    fn loop_variable_bounded<D: Data>(&mut self, summary: T::Summary, limit: T) -> LoopVariableParts<'a, G, T, D> {
+        let (mut handle, stream) = self.loop_variable(summary);
+        handle.limit = Some(Box::new(move |time| time.inner.less_equal(&limit)));
+        (handle, stream)
+    }
+}
+
+/// Circulates a `Stream` through a loop in a nested iterative scope.
+pub trait Iterate<G: Scope, D: Data> {
+    /// Applies `logic` to the stream and to the output of `logic` in each iteration, until no
+    /// records remain.
+    ///
+    /// The stream enters a nested scope whose timestamps count iterations, where it is merged
+    /// with a loop variable. The output of `logic` is returned to the loop variable, one
+    /// iteration later, and also leaves the scope; the resulting stream contains the records
+    /// produced by `logic` in every iteration. Records should eventually stop circulating, for
+    /// example by filtering them, as the loop is otherwise never complete.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Iterate, Map, Filter, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     // halve numbers until they are odd.
+    ///     vec![12, 20].to_stream(scope)
+    ///                 .iterate(|inner| inner.filter(|x| x % 2 == 0).map(|x| x / 2))
+    ///                 .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![3, 5, 6, 10])]);
+    /// ```
+    fn iterate<F>(&self, logic: F) -> Stream<G, D>
+    where
+        F: for<'a> FnOnce(&Stream<Iterative<'a, G, u64>, D>)->Stream<Iterative<'a, G, u64>, D>;
+}
+
+impl<G: Scope, D: Data> Iterate<G, D> for Stream<G, D> {
+    fn iterate<F>(&self, logic: F) -> Stream<G, D>
+    where
+        F: for<'a> FnOnce(&Stream<Iterative<'a, G, u64>, D>)->Stream<Iterative<'a, G, u64>, D>,
+    {
+        self.scope().iterative::<u64,_,_>(|inner| {
+            let (handle, cycle) = inner.loop_variable(1);
+            let result = logic(&self.enter(inner).concat(&cycle));
+            result.connect_loop(handle);
+            result.leave()
+        })
+    }
 }
 
 /// Connect a `Stream` to the input of a loop variable.
@@ -110,6 +219,7 @@ impl<G: Scope, D: Data> ConnectLoop<G, D> for Stream<G, D> {
 
         let mut builder = helper.builder;
         let summary = helper.summary;
+        let limit = helper.limit;
         let mut output = helper.output;
 
         let mut input = builder.new_input_connection(self, Pipeline, vec![Antichain::from_elem(summary.clone())]);
@@ -119,7 +229,8 @@ impl<G: Scope, D: Data> ConnectLoop<G, D> for Stream<G, D> {
             let mut output = output.activate();
             input.for_each(|cap, data| {
                 data.swap(&mut vector);
-                if let Some(new_time) = summary.results_in(cap.time()) {
+                let new_time = summary.results_in(cap.time()).filter(|time| limit.as_ref().map(|limit| limit(time)).unwrap_or(true));
+                if let Some(new_time) = new_time {
                     let new_cap = cap.delayed(&new_time);
                     output
                         .session(&new_cap)
@@ -135,4 +246,8 @@ pub struct Handle<G: Scope, D: Data> {
     builder: OperatorBuilder<G>,
     summary: <G::Timestamp as Timestamp>::Summary,
     output: OutputWrapper<G::Timestamp, D, Tee<G::Timestamp, D>>,
+    limit: Option<Limit<G::Timestamp>>,
 }
+
+/// A test of whether data at a time may continue to circulate.
+type Limit<T> = Box<dyn Fn(&T)->bool>;
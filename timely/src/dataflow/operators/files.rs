@@ -0,0 +1,171 @@
+//! Reading streams of lines from files, and writing streams to files.
+
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, VecDeque};
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::operator::{source, Operator};
+use crate::dataflow::{Stream, Scope};
+
+/// Reads the lines of files into a `Stream`.
+pub trait ReadLines<G: Scope<Timestamp=u64>> {
+    /// Reads the lines of `paths`, with each worker reading a share of the files.
+    ///
+    /// The files are assigned to workers in turn, and each worker reads its files in order.
+    /// Lines are produced in epochs of `lines_per_epoch`, starting from epoch zero and
+    /// continuing from one file to the next, with one epoch produced each time the operator is
+    /// scheduled. Failures to open or read a file cause a panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ReadLines, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let dir = std::env::temp_dir().join(format!("timely-read-lines-{}", std::process::id()));
+    /// std::fs::create_dir_all(&dir).unwrap();
+    /// std::fs::write(dir.join("a.txt"), "a1\na2\na3\n").unwrap();
+    /// std::fs::write(dir.join("b.txt"), "b1\nb2\n").unwrap();
+    ///
+    /// let paths = vec![dir.join("a.txt"), dir.join("b.txt")];
+    /// let captured = timely::example(move |scope| {
+    ///     scope.read_lines(&paths, 2)
+    ///          .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![
+    ///     (0, vec!["a1".to_string(), "a2".to_string()]),
+    ///     (1, vec!["a3".to_string(), "b1".to_string()]),
+    ///     (2, vec!["b2".to_string()]),
+    /// ]);
+    ///
+    /// std::fs::remove_dir_all(&dir).unwrap();
+    /// ```
+    fn read_lines<P: AsRef<Path>>(&mut self, paths: &[P], lines_per_epoch: usize) -> Stream<G, String>;
+}
+
+impl<G: Scope<Timestamp=u64>> ReadLines<G> for G {
+    fn read_lines<P: AsRef<Path>>(&mut self, paths: &[P], lines_per_epoch: usize) -> Stream<G, String> {
+
+        assert!(lines_per_epoch > 0, "epochs must contain at least one line");
+
+        let index = self.index();
+        let peers = self.peers();
+        let mut paths = paths.iter()
+                             .map(|path| path.as_ref().to_path_buf())
+                             .enumerate()
+                             .filter(|(position, _)| position % peers == index)
+                             .map(|(_, path)| path)
+                             .collect::<VecDeque<_>>();
+
+        source(self, "ReadLines", |capability, info| {
+
+            let activator = self.activator_for(&info.address[..]);
+
+            let mut capability = Some(capability);
+            let mut lines: Option<Lines<BufReader<File>>> = None;
+
+            move |output| {
+
+                let mut done = false;
+                if let Some(cap) = capability.as_mut() {
+                    let mut session = output.session(cap);
+                    let mut count = 0;
+                    while count < lines_per_epoch && !done {
+                        if lines.is_none() {
+                            match paths.pop_front() {
+                                Some(path) => {
+                                    let file = File::open(&path).unwrap_or_else(|e| panic!("Failed to open {:?}: {}", path, e));
+                                    lines = Some(BufReader::new(file).lines());
+                                },
+                                None => done = true,
+                            }
+                        }
+                        else if let Some(line) = lines.as_mut().unwrap().next() {
+                            session.give(line.expect("Failed to read line"));
+                            count += 1;
+                        }
+                        else {
+                            lines = None;
+                        }
+                    }
+                }
+
+                if done {
+                    capability = None;
+                }
+                else if let Some(cap) = capability.as_mut() {
+                    let next = *cap.time() + 1;
+                    cap.downgrade(&next);
+                    activator.activate();
+                }
+            }
+        })
+    }
+}
+
+/// Writes the records of a `Stream` to files.
+pub trait WriteFiles<G: Scope, D: Data+Display> {
+    /// Writes the records at each time to the file named by `path`, one record per line.
+    ///
+    /// The file for a time is created when its first records arrive at a worker, and is flushed
+    /// and closed once the input frontier has passed the time. As workers write their records to
+    /// separate files, `path` is called with both the time and the worker's index. Failures to
+    /// create or write a file cause a panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, WriteFiles};
+    ///
+    /// let dir = std::env::temp_dir().join(format!("timely-write-files-{}", std::process::id()));
+    /// std::fs::create_dir_all(&dir).unwrap();
+    ///
+    /// let dir2 = dir.clone();
+    /// timely::example(move |scope| {
+    ///     (0 .. 5).to_stream(scope)
+    ///             .delay(|x, _time| x % 2)
+    ///             .write_to_files(move |time, index| dir2.join(format!("{}-{}.txt", time, index)));
+    /// });
+    ///
+    /// assert_eq!(std::fs::read_to_string(dir.join("0-0.txt")).unwrap(), "0\n2\n4\n");
+    /// assert_eq!(std::fs::read_to_string(dir.join("1-0.txt")).unwrap(), "1\n3\n");
+    ///
+    /// std::fs::remove_dir_all(&dir).unwrap();
+    /// ```
+    fn write_to_files<F: Fn(&G::Timestamp, usize)->PathBuf+'static>(&self, path: F);
+}
+
+impl<G: Scope, D: Data+Display> WriteFiles<G, D> for Stream<G, D> {
+    fn write_to_files<F: Fn(&G::Timestamp, usize)->PathBuf+'static>(&self, path: F) {
+
+        let index = self.scope().index();
+        let mut files = HashMap::new();
+
+        self.sink(Pipeline, "WriteFiles", move |input| {
+
+            while let Some((time, data)) = input.next() {
+                let writer = files.entry(time.time().clone()).or_insert_with(|| {
+                    let path = path(time.time(), index);
+                    let file = File::create(&path).unwrap_or_else(|e| panic!("Failed to create {:?}: {}", path, e));
+                    BufWriter::new(file)
+                });
+                for datum in data.iter() {
+                    writeln!(writer, "{}", datum).expect("Failed to write record");
+                }
+            }
+
+            // close the files of times the frontier has passed.
+            let frontier = input.frontier();
+            files.retain(|time, writer| {
+                let open = frontier.less_equal(time);
+                if !open {
+                    writer.flush().expect("Failed to flush file");
+                }
+                open
+            });
+        });
+    }
+}
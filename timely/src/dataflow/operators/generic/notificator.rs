@@ -39,6 +39,29 @@ impl<'a, T: Timestamp> Notificator<'a, T> {
     }
 
     /// Reveals the elements in the frontier of the indicated input.
+    ///
+    /// The frontier is a lower bound on the times of records the input may yet receive, and can
+    /// be consulted without requesting a notification.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Inspect};
+    /// use timely::dataflow::operators::generic::Operator;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10u64).to_stream(scope)
+    ///               .delay(|x, _time| *x)
+    ///               .unary_notify(Pipeline, "example", Vec::new(), |input, output, notificator| {
+    ///                   input.for_each(|cap, data| {
+    ///                       // records are never at times before the frontier.
+    ///                       assert!(notificator.frontier(0).less_equal(cap.time()));
+    ///                       output.session(&cap).give_vec(&mut data.replace(Vec::new()));
+    ///                   });
+    ///               })
+    ///               .inspect(|x: &u64| assert!(*x < 10));
+    /// });
+    /// ```
     pub fn frontier(&self, input: usize) -> AntichainRef<T> {
         self.frontiers[input].frontier()
     }
@@ -173,6 +196,42 @@ fn notificator_delivers_notifications_in_topo_order() {
     }
 }
 
+#[test]
+fn frontier_notificator_delivers_times_not_in_advance_of_frontier() {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use crate::progress::ChangeBatch;
+    use crate::progress::frontier::MutableAntichain;
+    use crate::dataflow::operators::capability::mint as mint_capability;
+
+    let mut frontier = MutableAntichain::new_bottom(0u64);
+    let root_capability = mint_capability(0u64, Rc::new(RefCell::new(ChangeBatch::new())));
+
+    // request notifications out of order, with one time requested repeatedly.
+    let mut notificator = FrontierNotificator::new();
+    for time in &[4, 2, 7, 2, 3] {
+        notificator.notify_at(root_capability.delayed(time));
+    }
+
+    // nothing is available while the frontier is at zero.
+    assert!(notificator.next(&[&frontier]).is_none());
+    assert_eq!(notificator.pending().count(), 4);
+
+    // advance the frontier to 4, making 2 and 3 available, each exactly once and in order.
+    frontier.update_iter(vec![(0, -1), (4, 1)]);
+    let mut delivered = Vec::new();
+    notificator.for_each(&[&frontier], |cap, _| delivered.push(*cap.time()));
+    assert_eq!(delivered, vec![2, 3]);
+    assert_eq!(notificator.pending().map(|x| *x.0.time()).collect::<Vec<_>>(), vec![4, 7]);
+
+    // close the input, making all remaining times available.
+    frontier.update_iter(vec![(4, -1)]);
+    delivered.clear();
+    notificator.for_each(&[&frontier], |cap, _| delivered.push(*cap.time()));
+    assert_eq!(delivered, vec![4, 7]);
+    assert_eq!(notificator.pending().count(), 0);
+}
+
 /// Tracks requests for notification and delivers available notifications.
 ///
 /// `FrontierNotificator` is meant to manage the delivery of requested notifications in the
@@ -242,6 +242,62 @@ pub trait Operator<G: Scope, D1: Data> {
     ///    }
     /// }).unwrap();
     /// ```
+    ///
+    /// Notifications are delivered once a time is complete at all inputs, but the frontier of each
+    /// input is also available individually, through `notificator.frontier(input)`. Here records
+    /// from the first input are held only until the second input's frontier has passed their time,
+    /// regardless of the progress of the first input.
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use std::cell::RefCell;
+    /// use timely::dataflow::operators::{Input, Capture, Capability};
+    /// use timely::dataflow::operators::capture::Extract;
+    /// use timely::dataflow::operators::generic::operator::Operator;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::execute(timely::Configuration::Thread, |worker| {
+    ///    let events = Rc::new(RefCell::new(Vec::new()));
+    ///    let (mut in1, mut in2) = worker.dataflow::<usize,_,_>(|scope| {
+    ///        let (in1_handle, in1) = scope.new_input::<usize>();
+    ///        let (in2_handle, in2) = scope.new_input::<()>();
+    ///
+    ///        let mut held: Vec<(Capability<usize>, Vec<usize>)> = Vec::new();
+    ///        in1.binary_notify(&in2, Pipeline, Pipeline, "HoldUntil", None, move |input1, input2, output, notificator| {
+    ///            input1.for_each(|time, data| {
+    ///                held.push((time.retain(), data.replace(Vec::new())));
+    ///            });
+    ///            input2.for_each(|_time, _data| { });
+    ///            // release records at times the second input has passed.
+    ///            let frontier2 = notificator.frontier(1);
+    ///            let mut still_held = Vec::new();
+    ///            for (cap, mut data) in held.drain(..) {
+    ///                if frontier2.less_equal(cap.time()) {
+    ///                    still_held.push((cap, data));
+    ///                }
+    ///                else {
+    ///                    output.session(&cap).give_vec(&mut data);
+    ///                }
+    ///            }
+    ///            held = still_held;
+    ///        })
+    ///        .capture_into(events.clone());
+    ///
+    ///        (in1_handle, in2_handle)
+    ///    });
+    ///
+    ///    for i in 0..5 {
+    ///        in1.send(i);
+    ///        in1.advance_to(i + 1);
+    ///    }
+    ///    for _ in 0..10 { worker.step(); }
+    ///    assert!(events.borrow().clone().extract().is_empty());
+    ///
+    ///    in2.advance_to(3);
+    ///    for _ in 0..10 { worker.step(); }
+    ///    assert_eq!(events.borrow().clone().extract(), vec![(0, vec![0]), (1, vec![1]), (2, vec![2])]);
+    /// }).unwrap();
+    /// ```
     fn binary_notify<D2: Data,
               D3: Data,
               L: FnMut(&mut InputHandle<G::Timestamp, D1, P1::Puller>,
@@ -320,6 +376,49 @@ pub trait Operator<G: Scope, D1: Data> {
     ///         });
     /// });
     /// ```
+    ///
+    /// The operator has no outputs, and so neither holds capabilities nor maintains a `Tee`. The
+    /// input frontier can be used to determine when all data for a time have been received, for
+    /// example to flush writes to an external system once an epoch completes.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use std::collections::HashMap;
+    ///
+    /// use timely::dataflow::operators::{ToStream, Map};
+    /// use timely::dataflow::operators::generic::operator::Operator;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// let flushed = Arc::new(Mutex::new(Vec::new()));
+    /// let flushed2 = flushed.clone();
+    ///
+    /// timely::execute_directly(move |worker| {
+    ///     worker.dataflow::<u64,_,_>(move |scope| {
+    ///         let mut stash = HashMap::new();
+    ///         (0u64..10)
+    ///             .to_stream(scope)
+    ///             .map(|x| x % 3)
+    ///             .sink(Pipeline, "Flush", move |input| {
+    ///                 while let Some((time, data)) = input.next() {
+    ///                     stash.entry(time.time().clone())
+    ///                          .or_insert(Vec::new())
+    ///                          .extend(data.iter().cloned());
+    ///                 }
+    ///                 // flush each epoch no longer in advance of the frontier.
+    ///                 let frontier = input.frontier();
+    ///                 let mut complete = stash.keys().filter(|t| !frontier.less_equal(t)).cloned().collect::<Vec<_>>();
+    ///                 complete.sort();
+    ///                 for time in complete {
+    ///                     let mut batch = stash.remove(&time).unwrap();
+    ///                     batch.sort();
+    ///                     flushed2.lock().unwrap().push((time, batch));
+    ///                 }
+    ///             });
+    ///     });
+    /// });
+    ///
+    /// assert_eq!(*flushed.lock().unwrap(), vec![(0, vec![0, 0, 0, 0, 1, 1, 1, 2, 2, 2])]);
+    /// ```
     fn sink<L, P>(&self, pact: P, name: &str, logic: L)
     where
         L: FnMut(&mut FrontieredInputHandle<G::Timestamp, D1, P::Puller>)+'static,
@@ -2,17 +2,29 @@
 
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::time::{Duration, Instant};
+use std::io::{self, Read, Write, Seek, SeekFrom, BufWriter};
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::collections::HashMap;
 
-use crate::scheduling::{Schedule, Activator};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::scheduling::{Schedule, Activator, SyncActivator};
 
 use crate::progress::frontier::Antichain;
-use crate::progress::{Operate, operate::SharedProgress, Timestamp, ChangeBatch};
+use crate::progress::{Operate, operate::SharedProgress, Timestamp, PathSummary, ChangeBatch};
 use crate::progress::Source;
 
 use crate::Data;
 use crate::communication::Push;
 use crate::dataflow::{Stream, ScopeParent, Scope};
 use crate::dataflow::channels::{Message, pushers::{Tee, Counter}};
+use crate::dataflow::operators::generic::operator::source;
 
 // TODO : This is an exogenous input, but it would be nice to wrap a Subgraph in something
 // TODO : more like a harness, with direct access to its inputs.
@@ -89,6 +101,46 @@ pub trait Input : Scope {
     /// });
     /// ```
     fn input_from<D: Data>(&mut self, handle: &mut Handle<<Self as ScopeParent>::Timestamp, D>) -> Stream<Self, D>;
+
+    /// Create a new `Stream` and an `InputProxy` through which other threads can supply input.
+    ///
+    /// The `InputProxy` can be moved to and cloned across threads. Each proxy has its own epoch,
+    /// and the stream's frontier is the least epoch of the proxies that have not been dropped.
+    /// Records and epoch advancements are sent over a channel, which the operator drains each
+    /// time it is scheduled, and the proxies activate the operator and unpark the worker as they
+    /// send. The stream is complete once all proxies are dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{Input, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::execute_directly(|worker| {
+    ///     let (proxy, captured) = worker.dataflow::<u64,_,_>(|scope| {
+    ///         let (proxy, stream) = scope.new_input_proxy();
+    ///         (proxy, stream.capture())
+    ///     });
+    ///
+    ///     // feed the input from two other threads, each with its own proxy.
+    ///     let threads = (0 .. 2u64).map(|index| {
+    ///         let mut proxy = proxy.clone();
+    ///         std::thread::spawn(move || {
+    ///             for round in 0 .. 3 {
+    ///                 proxy.send(10 * round + index);
+    ///                 proxy.advance_to(round + 1);
+    ///             }
+    ///         })
+    ///     }).collect::<Vec<_>>();
+    ///     drop(proxy);
+    ///
+    ///     while worker.step_or_park(None) { }
+    ///     for thread in threads { thread.join().unwrap(); }
+    ///     captured
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![0, 1]), (1, vec![10, 11]), (2, vec![20, 21])]);
+    /// ```
+    fn new_input_proxy<D: Data+Send>(&mut self) -> (InputProxy<<Self as ScopeParent>::Timestamp, D>, Stream<Self, D>);
 }
 
 use crate::order::TotalOrder;
@@ -128,6 +180,52 @@ impl<G: Scope> Input for G where <G as ScopeParent>::Timestamp: TotalOrder {
 
         Stream::new(Source::new(index, 0), registrar, self.clone())
     }
+
+    fn new_input_proxy<D: Data+Send>(&mut self) -> (InputProxy<<G as ScopeParent>::Timestamp, D>, Stream<G, D>) {
+
+        let (sender, receiver) = mpsc::channel();
+        let mut activator = None;
+
+        let stream = source(self, "InputProxy", |capability, info| {
+
+            activator = Some(self.sync_activator_for(&info.address[..]));
+
+            let mut capability = Some(capability);
+            // the epochs of proxies that have not been dropped, by identifier.
+            let mut epochs = HashMap::new();
+            epochs.insert(0, <G as ScopeParent>::Timestamp::minimum());
+
+            move |output| {
+                while let Ok(command) = receiver.try_recv() {
+                    match command {
+                        ProxyCommand::Batch(time, mut data) => {
+                            if let Some(cap) = capability.as_ref() {
+                                output.session(&cap.delayed(&time)).give_vec(&mut data);
+                            }
+                        },
+                        ProxyCommand::Advance(identifier, time) => { epochs.insert(identifier, time); },
+                        ProxyCommand::Close(identifier) => { epochs.remove(&identifier); },
+                    }
+                }
+
+                match epochs.values().min() {
+                    Some(time) => { if let Some(cap) = capability.as_mut() { cap.downgrade(time); } },
+                    None => capability = None,
+                }
+            }
+        });
+
+        let proxy = InputProxy {
+            identifier: 0,
+            identifiers: Arc::new(AtomicUsize::new(1)),
+            sender,
+            activator: activator.expect("source constructor not called"),
+            buffer: Vec::with_capacity(Message::<<G as ScopeParent>::Timestamp, D>::default_length()),
+            now_at: <G as ScopeParent>::Timestamp::minimum(),
+        };
+
+        (proxy, stream)
+    }
 }
 
 struct Operator<T:Timestamp> {
@@ -176,6 +274,7 @@ pub struct Handle<T: Timestamp, D: Data> {
     buffer1: Vec<D>,
     buffer2: Vec<D>,
     now_at: T,
+    auto_advance: Option<(Duration, T::Summary, Instant)>,
 }
 
 impl<T:Timestamp, D: Data> Handle<T, D> {
@@ -214,6 +313,7 @@ impl<T:Timestamp, D: Data> Handle<T, D> {
             buffer1: Vec::with_capacity(Message::<T, D>::default_length()),
             buffer2: Vec::with_capacity(Message::<T, D>::default_length()),
             now_at: T::minimum(),
+            auto_advance: None,
         }
     }
 
@@ -352,10 +452,109 @@ impl<T:Timestamp, D: Data> Handle<T, D> {
         }
     }
 
+    /// Advances the current epoch by `summary` each time `period` of wall-clock time elapses.
+    ///
+    /// The epoch is only advanced by calls to `tick`, which a driver should make regularly, for
+    /// example before each call to `worker.step()`. The epoch may still be advanced explicitly
+    /// with `advance_to`, and wall-clock periods continue to be measured from the last tick.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use timely::*;
+    /// use timely::dataflow::operators::{Input, Inspect, Probe};
+    /// use timely::dataflow::operators::input::Handle;
+    ///
+    /// timely::execute(Configuration::Thread, |worker| {
+    ///
+    ///     let mut input = Handle::<u64, u64>::new();
+    ///     let probe = worker.dataflow(|scope| {
+    ///         scope.input_from(&mut input)
+    ///              .inspect(|x| println!("hello {:?}", x))
+    ///              .probe()
+    ///     });
+    ///
+    ///     // advance the epoch by one every millisecond.
+    ///     input.auto_advance(Duration::from_millis(1), 1);
+    ///
+    ///     let start = Instant::now();
+    ///     while *input.time() < 20 {
+    ///         input.send(*input.time());
+    ///         input.tick();
+    ///         worker.step();
+    ///     }
+    ///
+    ///     assert!(start.elapsed() >= Duration::from_millis(19));
+    ///     worker.step_while(|| probe.less_than(input.time()));
+    /// }).unwrap();
+    /// ```
+    pub fn auto_advance(&mut self, period: Duration, summary: T::Summary) {
+        assert!(period > Duration::from_secs(0), "auto-advance period must be positive");
+        self.auto_advance = Some((period, summary, Instant::now()));
+    }
+
+    /// Advances the current epoch for each period elapsed since the last advance.
+    ///
+    /// This method has no effect unless `auto_advance` has been called.
+    pub fn tick(&mut self) {
+        if let Some((period, summary, last)) = self.auto_advance.take() {
+            let elapsed = last.elapsed();
+            let periods = elapsed.as_nanos() / period.as_nanos();
+            let mut next = self.now_at.clone();
+            for _ in 0 .. periods {
+                match summary.results_in(&next) {
+                    Some(time) => next = time,
+                    None => break,
+                }
+            }
+            self.advance_to(next);
+            // the time since the last whole period counts towards the next one.
+            let remainder = Duration::from_nanos((elapsed.as_nanos() % period.as_nanos()) as u64);
+            self.auto_advance = Some((period, summary, last + elapsed - remainder));
+        }
+    }
+
     /// Closes the input.
     ///
     /// This method allows timely dataflow to issue all progress notifications blocked by this input
     /// and to begin to shut down operators, as this input can no longer produce data.
+    ///
+    /// Closing every input is how a streaming computation shuts down gracefully: once all inputs
+    /// are closed, the dataflows drain the records in flight, their frontiers become empty, and
+    /// `worker.step()` returns `false`. When every worker's closure then returns, the threads
+    /// that connect processes finish sending and exit.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use timely::*;
+    /// use timely::dataflow::operators::{Input, Inspect};
+    /// use timely::dataflow::operators::input::Handle;
+    ///
+    /// // a flag another thread might set to request shutdown.
+    /// let shutdown = Arc::new(AtomicBool::new(false));
+    ///
+    /// let guards = timely::execute(Configuration::Process(2), move |worker| {
+    ///
+    ///     let mut input = Handle::<u64, u64>::new();
+    ///     worker.dataflow(|scope| { scope.input_from(&mut input).inspect(|_| ()); });
+    ///
+    ///     while !shutdown.load(Ordering::SeqCst) {
+    ///         input.send(*input.time());
+    ///         let next = *input.time() + 1;
+    ///         input.advance_to(next);
+    ///         worker.step();
+    ///         if next == 100 { shutdown.store(true, Ordering::SeqCst); }
+    ///     }
+    ///
+    ///     // close the input, and run until all records are processed.
+    ///     input.close();
+    ///     while worker.step() { }
+    /// }).unwrap();
+    ///
+    /// assert!(guards.join().into_iter().all(|result| result.is_ok()));
+    /// ```
     pub fn close(self) { }
 
     /// Reports the current epoch.
@@ -367,6 +566,101 @@ impl<T:Timestamp, D: Data> Handle<T, D> {
     pub fn time(&self) -> &T {
         &self.now_at
     }
+
+    /// Acquires a token that holds back the frontier of the input at the current epoch.
+    ///
+    /// While the token exists, the frontiers of the streams created from the handle do not pass
+    /// the time of the token, even as the handle advances or closes. This allows code outside the
+    /// dataflow, for example a request handler that has accepted work it has not yet submitted,
+    /// to prevent downstream operators from considering the time complete. The token may be
+    /// downgraded to later times, and releases its hold when dropped. It only holds the streams
+    /// created from the handle before it was acquired.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{Input, Probe};
+    ///
+    /// timely::execute_directly(|worker| {
+    ///
+    ///     let (mut input, probe) = worker.dataflow::<u64,_,_>(|scope| {
+    ///         let (input, stream) = scope.new_input::<u64>();
+    ///         (input, stream.probe())
+    ///     });
+    ///
+    ///     // hold the frontier at epoch zero while the input advances.
+    ///     let mut hold = input.hold();
+    ///     input.advance_to(5);
+    ///     for _ in 0 .. 10 { worker.step(); }
+    ///     assert!(probe.less_equal(&0));
+    ///
+    ///     // release epochs before the third.
+    ///     hold.downgrade(&3);
+    ///     worker.step_while(|| probe.less_than(&3));
+    ///     assert!(!probe.less_equal(&2));
+    ///     assert!(probe.less_equal(&3));
+    ///
+    ///     // release the remaining epochs.
+    ///     drop(hold);
+    ///     worker.step_while(|| probe.less_than(&5));
+    ///     assert!(!probe.less_equal(&4));
+    ///     assert!(probe.less_equal(&5));
+    /// });
+    /// ```
+    pub fn hold(&self) -> Hold<T> {
+        for progress in self.progress.iter() {
+            progress.borrow_mut().update(self.now_at.clone(), 1);
+        }
+        Hold {
+            time: self.now_at.clone(),
+            progress: self.progress.clone(),
+            activate: self.activate.clone(),
+        }
+    }
+}
+
+/// A token holding back the frontier of an input, acquired by `Handle::hold`.
+pub struct Hold<T: Timestamp> {
+    time: T,
+    progress: Vec<Rc<RefCell<ChangeBatch<T>>>>,
+    activate: Vec<Activator>,
+}
+
+impl<T: Timestamp> Hold<T> {
+    /// The time at which the frontier is held.
+    pub fn time(&self) -> &T {
+        &self.time
+    }
+
+    /// Downgrades the hold to a later time, releasing the times it no longer holds.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `time` is not greater or equal to the time of the hold.
+    pub fn downgrade(&mut self, time: &T) {
+        assert!(self.time.less_equal(time), "Attempted to downgrade a hold to an earlier time");
+        if !self.time.eq(time) {
+            for progress in self.progress.iter() {
+                let mut borrow = progress.borrow_mut();
+                borrow.update(time.clone(), 1);
+                borrow.update(self.time.clone(), -1);
+            }
+            self.time = time.clone();
+            for activate in self.activate.iter() {
+                activate.activate();
+            }
+        }
+    }
+}
+
+impl<T: Timestamp> Drop for Hold<T> {
+    fn drop(&mut self) {
+        for progress in self.progress.iter() {
+            progress.borrow_mut().update(self.time.clone(), -1);
+        }
+        for activate in self.activate.iter() {
+            activate.activate();
+        }
+    }
 }
 
 impl<T:Timestamp, D: Data> Drop for Handle<T, D> {
@@ -374,3 +668,305 @@ impl<T:Timestamp, D: Data> Drop for Handle<T, D> {
         self.close_epoch();
     }
 }
+
+/// An entry of the log kept by a `DurableHandle`.
+#[derive(Serialize, Deserialize)]
+enum LogEntry<T, D> {
+    /// Records sent at a time.
+    Batch(T, Vec<D>),
+    /// An advance of the input to a time.
+    Advance(T),
+}
+
+// the bytes of the length and checksum that precede each entry in the log.
+const ENTRY_HEADER: usize = 8;
+
+/// The CRC-32 (IEEE) checksum of `bytes`.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0 .. 8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+/// An input handle which records its input in a log on disk, and replays the log when reopened.
+///
+/// Records are written to the log before they are introduced to the dataflow, and the log is
+/// flushed and synchronized with the disk whenever the input is advanced; each completed epoch is
+/// therefore durable. When the handle is opened, the log's contents are replayed into the
+/// dataflow before new input is accepted, so a computation whose operators are deterministic
+/// recovers its state by being rebuilt and re-fed. Records sent at the epoch in progress when a
+/// computation stops may or may not be replayed.
+///
+/// Each entry is serialized with `bincode` and preceded by its length and a CRC-32 checksum, so
+/// the log does not depend on the memory layout of the types, and a log written by one build can
+/// be read by another. Replay stops at the first entry that is incomplete or fails its checksum,
+/// and the log is truncated there, so a partially written entry is discarded.
+///
+/// Handles should be opened once the dataflow reading from the wrapped handle is constructed,
+/// as records replayed before then are not received by the dataflow.
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+/// use std::cell::RefCell;
+/// use timely::dataflow::operators::{Input, Inspect};
+/// use timely::dataflow::operators::input::{Handle, DurableHandle};
+///
+/// let path = std::env::temp_dir().join(format!("timely-durable-{}.log", std::process::id()));
+///
+/// // the records a computation receives, after first running for three epochs.
+/// let run = |epochs: u64| {
+///     let path = path.clone();
+///     timely::execute_directly(move |worker| {
+///         let received = Rc::new(RefCell::new(Vec::new()));
+///         let received2 = received.clone();
+///         let mut input = Handle::<u64, u64>::new();
+///         worker.dataflow(|scope| {
+///             scope.input_from(&mut input)
+///                  .inspect_time(move |time, x| received2.borrow_mut().push((*time, *x)));
+///         });
+///         let mut input = DurableHandle::open(input, &path).unwrap();
+///         for _ in 0 .. epochs {
+///             let time = *input.time();
+///             input.send(time * 10);
+///             input.advance_to(time + 1);
+///         }
+///         input.close().unwrap();
+///         while worker.step() { }
+///         received.replace(Vec::new())
+///     })
+/// };
+///
+/// assert_eq!(run(3), vec![(0, 0), (1, 10), (2, 20)]);
+/// // a restarted computation first replays the logged epochs.
+/// assert_eq!(run(1), vec![(0, 0), (1, 10), (2, 20), (3, 30)]);
+///
+/// std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct DurableHandle<T: Timestamp+Serialize+DeserializeOwned, D: Data+Serialize+DeserializeOwned> {
+    handle: Handle<T, D>,
+    log: BufWriter<File>,
+    buffer: Vec<D>,
+}
+
+impl<T: Timestamp+Serialize+DeserializeOwned, D: Data+Serialize+DeserializeOwned> DurableHandle<T, D> {
+    /// Opens the log at `path`, creating it if it does not exist, and replays its contents into
+    /// `handle`.
+    pub fn open<P: AsRef<Path>>(mut handle: Handle<T, D>, path: P) -> io::Result<Self> {
+
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+
+        // replay intact entries, and discard whatever follows the first entry that is not intact.
+        let mut valid = 0;
+        while let Some((entry, length)) = Self::read_entry(&bytes[valid..]) {
+            match entry {
+                LogEntry::Batch(time, mut data) => {
+                    handle.advance_to(time);
+                    handle.send_batch(&mut data);
+                },
+                LogEntry::Advance(time) => handle.advance_to(time),
+            }
+            valid += length;
+        }
+        file.set_len(valid as u64)?;
+        file.seek(SeekFrom::Start(valid as u64))?;
+
+        Ok(DurableHandle {
+            handle,
+            log: BufWriter::new(file),
+            buffer: Vec::with_capacity(Message::<T, D>::default_length()),
+        })
+    }
+
+    /// Sends one record into the dataflow at the current epoch, once it is written to the log.
+    pub fn send(&mut self, data: D) {
+        self.buffer.push(data);
+        if self.buffer.len() == self.buffer.capacity() {
+            self.flush().expect("Durable input log write failed");
+        }
+    }
+
+    /// Advances the current epoch to `next`, once the log is synchronized with the disk.
+    pub fn advance_to(&mut self, next: T) {
+        assert!(self.handle.time().less_equal(&next));
+        if !self.handle.time().eq(&next) {
+            self.sync(Some(next.clone())).expect("Durable input log write failed");
+            self.handle.advance_to(next);
+        }
+    }
+
+    /// Reports the current timestamp.
+    pub fn time(&self) -> &T {
+        self.handle.time()
+    }
+
+    /// Closes the input, once buffered records are written to the log and the log is
+    /// synchronized with the disk.
+    ///
+    /// Dropping the handle also writes buffered records to the log, but neither synchronizes
+    /// the log nor reports errors; `close` should be preferred where the records matter.
+    pub fn close(mut self) -> io::Result<()> {
+        self.sync(None)
+    }
+
+    // writes buffered records and an optional advance to the log, and synchronizes it with the disk.
+    fn sync(&mut self, next: Option<T>) -> io::Result<()> {
+        self.flush()?;
+        if let Some(next) = next {
+            self.write(&LogEntry::Advance(next))?;
+        }
+        self.log.flush()?;
+        self.log.get_ref().sync_data()
+    }
+
+    // writes buffered records to the log, and then sends them into the dataflow.
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            let entry = LogEntry::Batch(self.handle.time().clone(), ::std::mem::take(&mut self.buffer));
+            let written = self.write(&entry);
+            if let LogEntry::Batch(_, mut data) = entry {
+                if written.is_ok() {
+                    self.handle.send_batch(&mut data);
+                }
+                self.buffer = data;
+            }
+            written?;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, entry: &LogEntry<T, D>) -> io::Result<()> {
+        let bytes = bincode::serialize(entry).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        if bytes.len() > u32::MAX as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Durable input log entry too large"));
+        }
+        self.log.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        self.log.write_all(&crc32(&bytes).to_le_bytes())?;
+        self.log.write_all(&bytes)
+    }
+
+    // reads the entry at the start of `bytes` and its length in bytes, if it is intact.
+    fn read_entry(bytes: &[u8]) -> Option<(LogEntry<T, D>, usize)> {
+        if bytes.len() < ENTRY_HEADER { return None; }
+        let length = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        let checksum = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+        let payload = bytes.get(ENTRY_HEADER .. ENTRY_HEADER + length)?;
+        if crc32(payload) != checksum { return None; }
+        let entry = bincode::deserialize(payload).ok()?;
+        Some((entry, ENTRY_HEADER + length))
+    }
+}
+
+impl<T: Timestamp+Serialize+DeserializeOwned, D: Data+Serialize+DeserializeOwned> Drop for DurableHandle<T, D> {
+    fn drop(&mut self) {
+        // errors cannot be reported here; `close` reports them.
+        let _ = self.flush();
+        let _ = self.log.flush();
+    }
+}
+
+/// Commands sent from an `InputProxy` to its operator.
+enum ProxyCommand<T, D> {
+    /// Records at a time no less than the sending proxy's epoch.
+    Batch(T, Vec<D>),
+    /// Sets the epoch of the identified proxy, registering it if it is new.
+    Advance(usize, T),
+    /// Indicates that the identified proxy has been dropped.
+    Close(usize),
+}
+
+/// A thread-safe handle to an input `Stream`, created by `Input::new_input_proxy`.
+///
+/// Each proxy buffers records and sends them, with its epoch advancements, to the worker that
+/// hosts the input. Cloning a proxy creates another proxy at the same epoch, which must also be
+/// advanced or dropped for the stream's frontier to advance.
+pub struct InputProxy<T: Timestamp, D: Data+Send> {
+    identifier: usize,
+    identifiers: Arc<AtomicUsize>,
+    sender: Sender<ProxyCommand<T, D>>,
+    activator: SyncActivator,
+    buffer: Vec<D>,
+    now_at: T,
+}
+
+impl<T: Timestamp, D: Data+Send> InputProxy<T, D> {
+
+    /// Sends one record into the dataflow at the current epoch.
+    pub fn send(&mut self, data: D) {
+        self.buffer.push(data);
+        if self.buffer.len() == self.buffer.capacity() {
+            self.flush();
+        }
+    }
+
+    /// Sends a batch of records into the dataflow at the current epoch, leaving `buffer` empty.
+    pub fn send_batch(&mut self, buffer: &mut Vec<D>) {
+        if !buffer.is_empty() {
+            self.flush();
+            self.command(ProxyCommand::Batch(self.now_at.clone(), ::std::mem::take(buffer)));
+        }
+    }
+
+    /// Sends buffered records to the worker, without waiting for the buffer to fill.
+    pub fn flush(&mut self) {
+        if !self.buffer.is_empty() {
+            let data = ::std::mem::replace(&mut self.buffer, Vec::with_capacity(Message::<T, D>::default_length()));
+            self.command(ProxyCommand::Batch(self.now_at.clone(), data));
+        }
+    }
+
+    /// Advances the current epoch to `next`, after sending any buffered records.
+    pub fn advance_to(&mut self, next: T) {
+        assert!(self.now_at.less_equal(&next));
+        if !self.now_at.eq(&next) {
+            self.flush();
+            self.now_at = next;
+            self.command(ProxyCommand::Advance(self.identifier, self.now_at.clone()));
+        }
+    }
+
+    /// Reports the current epoch.
+    pub fn time(&self) -> &T {
+        &self.now_at
+    }
+
+    /// Closes the proxy, after sending any buffered records.
+    pub fn close(self) { }
+
+    // sends a command and activates the operator; errors indicate the worker has shut down.
+    fn command(&self, command: ProxyCommand<T, D>) {
+        if self.sender.send(command).is_ok() {
+            let _ = self.activator.activate();
+        }
+    }
+}
+
+impl<T: Timestamp, D: Data+Send> Clone for InputProxy<T, D> {
+    fn clone(&self) -> Self {
+        let identifier = self.identifiers.fetch_add(1, Ordering::SeqCst);
+        // registered before this proxy can be dropped, so the epoch is still held.
+        self.command(ProxyCommand::Advance(identifier, self.now_at.clone()));
+        InputProxy {
+            identifier,
+            identifiers: self.identifiers.clone(),
+            sender: self.sender.clone(),
+            activator: self.activator.clone(),
+            buffer: Vec::with_capacity(Message::<T, D>::default_length()),
+            now_at: self.now_at.clone(),
+        }
+    }
+}
+
+impl<T: Timestamp, D: Data+Send> Drop for InputProxy<T, D> {
+    fn drop(&mut self) {
+        self.flush();
+        self.command(ProxyCommand::Close(self.identifier));
+    }
+}
@@ -45,6 +45,8 @@ pub trait Inspect<G: Scope, D: Data> {
 
     /// Runs a supplied closure on each observed data batch (time and data slice).
     ///
+    /// Each batch is forwarded downstream as received, without copying its records.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::operators::{ToStream, Map, Inspect};
@@ -54,6 +56,29 @@ pub trait Inspect<G: Scope, D: Data> {
     ///            .inspect_batch(|t,xs| println!("seen at: {:?}\t{:?} records", t, xs.len()));
     /// });
     /// ```
+    ///
+    /// The closure observes records without changing the stream.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Delay, Inspect, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let observed = Arc::new(Mutex::new(Vec::new()));
+    /// let observed2 = observed.clone();
+    ///
+    /// let captured = timely::example(move |scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .delay(|x, _time| x / 5)
+    ///            .inspect_batch(move |t, xs| observed2.lock().unwrap().push((*t, xs.len())))
+    ///            .capture()
+    /// });
+    ///
+    /// let mut observed = observed.lock().unwrap().clone();
+    /// observed.sort();
+    /// assert_eq!(observed, vec![(0, 5), (1, 5)]);
+    /// assert_eq!(captured.extract(), vec![(0, vec![0, 1, 2, 3, 4]), (1, vec![5, 6, 7, 8, 9])]);
+    /// ```
     fn inspect_batch(&self, func: impl FnMut(&G::Timestamp, &[D])+'static) -> Stream<G, D>;
 }
 
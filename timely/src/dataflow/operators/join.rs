@@ -0,0 +1,339 @@
+//! Joins and groups two keyed streams within each timestamp, or within intervals of timestamps.
+use std::rc::Rc;
+use std::hash::Hash;
+use std::collections::{HashMap, BTreeMap};
+
+use crate::{Data, ExchangeData};
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::channels::pact::Exchange;
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Joins two keyed streams within each timestamp.
+pub trait Join<G: Scope, K: ExchangeData+Hash+Eq, V1: ExchangeData> {
+    /// Joins records of the form `(key, val1)` and `(key, val2)` with equal keys and times.
+    ///
+    /// Both inputs are exchanged by `hash` applied to their keys, and matching pairs of
+    /// records are presented to `result`. Results are produced as soon as both records
+    /// have arrived, and the state retained for each time is discarded once that time is
+    /// complete on both inputs.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Join, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     let names = vec![(0, 'a'), (1, 'b'), (2, 'c')].to_stream(scope);
+    ///     (0..6).to_stream(scope)
+    ///           .map(|x| (x % 3, x))
+    ///           .join(&names, |key| *key as u64, |_key, val, name| (*val, *name))
+    ///           .capture()
+    /// });
+    ///
+    /// let extracted = captured.extract();
+    /// assert_eq!(extracted[0].1, vec![(0, 'a'), (1, 'b'), (2, 'c'), (3, 'a'), (4, 'b'), (5, 'c')]);
+    /// ```
+    ///
+    /// Records only match other records at the same time.
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{Input, Join, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (send, recv) = ::std::sync::mpsc::channel();
+    /// timely::execute(timely::Configuration::Thread, move |worker| {
+    ///     let send = send.clone();
+    ///     let (mut input1, mut input2) = worker.dataflow::<u64,_,_>(move |scope| {
+    ///         let (input1, stream1) = scope.new_input::<(u64, u64)>();
+    ///         let (input2, stream2) = scope.new_input::<(u64, u64)>();
+    ///         stream1.join(&stream2, |key| *key, |key, val1, val2| (*key, *val1, *val2))
+    ///                .capture_into(send);
+    ///         (input1, input2)
+    ///     });
+    ///     input1.send((0, 1));
+    ///     input2.send((0, 2));
+    ///     input1.advance_to(1);
+    ///     input2.advance_to(1);
+    ///     input2.send((0, 3));
+    /// }).unwrap();
+    ///
+    /// assert_eq!(recv.extract(), vec![(0, vec![(0, 1, 2)])]);
+    /// ```
+    fn join<V2, R, H, F>(&self, other: &Stream<G, (K, V2)>, hash: H, result: F) -> Stream<G, R>
+    where
+        V2: ExchangeData,
+        R: Data,
+        H: Fn(&K)->u64+'static,
+        F: Fn(&K, &V1, &V2)->R+'static;
+}
+
+impl<G: Scope, K: ExchangeData+Hash+Eq, V1: ExchangeData> Join<G, K, V1> for Stream<G, (K, V1)> {
+
+    fn join<V2, R, H, F>(&self, other: &Stream<G, (K, V2)>, hash: H, result: F) -> Stream<G, R>
+    where
+        V2: ExchangeData,
+        R: Data,
+        H: Fn(&K)->u64+'static,
+        F: Fn(&K, &V1, &V2)->R+'static {
+
+        let hash1 = Rc::new(hash);
+        let hash2 = hash1.clone();
+        let exchange1 = Exchange::new(move |x: &(K, V1)| hash1(&x.0));
+        let exchange2 = Exchange::new(move |x: &(K, V2)| hash2(&x.0));
+
+        // per-time maps from keys to the values received on each input.
+        let mut state1: HashMap<G::Timestamp, HashMap<K, Vec<V1>>> = HashMap::new();
+        let mut state2: HashMap<G::Timestamp, HashMap<K, Vec<V2>>> = HashMap::new();
+
+        let mut vector1 = Vec::new();
+        let mut vector2 = Vec::new();
+
+        self.binary_notify(other, exchange1, exchange2, "Join", vec![], move |input1, input2, output, notificator| {
+
+            // drain the first input, probe the second input's state, record values.
+            input1.for_each(|time, data| {
+                data.swap(&mut vector1);
+                let mut session = output.session(&time);
+                let probe = state2.get(time.time());
+                let store = state1.entry(time.time().clone()).or_insert_with(HashMap::new);
+                for (key, val1) in vector1.drain(..) {
+                    if let Some(vals2) = probe.and_then(|map| map.get(&key)) {
+                        for val2 in vals2.iter() {
+                            session.give(result(&key, &val1, val2));
+                        }
+                    }
+                    store.entry(key).or_insert_with(Vec::new).push(val1);
+                }
+                notificator.notify_at(time.retain());
+            });
+
+            // drain the second input, probe the first input's state, record values.
+            input2.for_each(|time, data| {
+                data.swap(&mut vector2);
+                let mut session = output.session(&time);
+                let probe = state1.get(time.time());
+                let store = state2.entry(time.time().clone()).or_insert_with(HashMap::new);
+                for (key, val2) in vector2.drain(..) {
+                    if let Some(vals1) = probe.and_then(|map| map.get(&key)) {
+                        for val1 in vals1.iter() {
+                            session.give(result(&key, val1, &val2));
+                        }
+                    }
+                    store.entry(key).or_insert_with(Vec::new).push(val2);
+                }
+                notificator.notify_at(time.retain());
+            });
+
+            // discard state for completed times.
+            notificator.for_each(|time, _, _| {
+                state1.remove(time.time());
+                state2.remove(time.time());
+            });
+        })
+    }
+}
+
+/// Joins two keyed streams whose timestamps are within an interval of each other.
+pub trait IntervalJoin<G: Scope<Timestamp=u64>, K: ExchangeData+Hash+Eq, V1: ExchangeData> {
+    /// Joins records of the form `(key, val1)` and `(key, val2)` with equal keys and times that
+    /// differ by at most `within`.
+    ///
+    /// Both inputs are exchanged by `hash` applied to their keys, and matching pairs of
+    /// records are presented to `result`. Each result is produced at the later of the times of
+    /// its two records, as soon as both records have arrived. Records are retained until the
+    /// frontier of the other input has passed their time plus `within`, after which they can
+    /// match no further records.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Map, IntervalJoin, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     let views = vec![(0, 'x', 2), (0, 'y', 9), (1, 'z', 6)]
+    ///         .to_stream(scope)
+    ///         .delay(|x, _time| x.2)
+    ///         .map(|(key, val, _time)| (key, val));
+    ///     vec![(0, 'a', 1), (0, 'b', 5), (1, 'c', 4)]
+    ///         .to_stream(scope)
+    ///         .delay(|x, _time| x.2)
+    ///         .map(|(key, val, _time)| (key, val))
+    ///         .interval_join(&views, 2, |key| *key as u64, |key, click, view| (*key, *click, *view))
+    ///         .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(2, vec![(0, 'a', 'x')]), (6, vec![(1, 'c', 'z')])]);
+    /// ```
+    fn interval_join<V2, R, H, F>(&self, other: &Stream<G, (K, V2)>, within: u64, hash: H, result: F) -> Stream<G, R>
+    where
+        V2: ExchangeData,
+        R: Data,
+        H: Fn(&K)->u64+'static,
+        F: Fn(&K, &V1, &V2)->R+'static;
+}
+
+impl<G: Scope<Timestamp=u64>, K: ExchangeData+Hash+Eq, V1: ExchangeData> IntervalJoin<G, K, V1> for Stream<G, (K, V1)> {
+
+    fn interval_join<V2, R, H, F>(&self, other: &Stream<G, (K, V2)>, within: u64, hash: H, result: F) -> Stream<G, R>
+    where
+        V2: ExchangeData,
+        R: Data,
+        H: Fn(&K)->u64+'static,
+        F: Fn(&K, &V1, &V2)->R+'static {
+
+        let hash1 = Rc::new(hash);
+        let hash2 = hash1.clone();
+        let exchange1 = Exchange::new(move |x: &(K, V1)| hash1(&x.0));
+        let exchange2 = Exchange::new(move |x: &(K, V2)| hash2(&x.0));
+
+        // per-time maps from keys to the values received on each input, ordered by time.
+        let mut state1: BTreeMap<u64, HashMap<K, Vec<V1>>> = BTreeMap::new();
+        let mut state2: BTreeMap<u64, HashMap<K, Vec<V2>>> = BTreeMap::new();
+
+        let mut vector1 = Vec::new();
+        let mut vector2 = Vec::new();
+
+        self.binary_frontier(other, exchange1, exchange2, "IntervalJoin", move |_, _| move |input1, input2, output| {
+
+            // drain the first input, probe the second input's state within the interval, record values.
+            input1.for_each(|time, data| {
+                data.swap(&mut vector1);
+                let time1 = *time.time();
+                let range = time1.saturating_sub(within) ..= time1.saturating_add(within);
+                for (key, val1) in vector1.drain(..) {
+                    for (time2, map) in state2.range(range.clone()) {
+                        if let Some(vals2) = map.get(&key) {
+                            let cap = time.delayed(&::std::cmp::max(time1, *time2));
+                            let mut session = output.session(&cap);
+                            for val2 in vals2.iter() {
+                                session.give(result(&key, &val1, val2));
+                            }
+                        }
+                    }
+                    state1.entry(time1).or_default().entry(key).or_default().push(val1);
+                }
+            });
+
+            // drain the second input, probe the first input's state within the interval, record values.
+            input2.for_each(|time, data| {
+                data.swap(&mut vector2);
+                let time2 = *time.time();
+                let range = time2.saturating_sub(within) ..= time2.saturating_add(within);
+                for (key, val2) in vector2.drain(..) {
+                    for (time1, map) in state1.range(range.clone()) {
+                        if let Some(vals1) = map.get(&key) {
+                            let cap = time.delayed(&::std::cmp::max(*time1, time2));
+                            let mut session = output.session(&cap);
+                            for val1 in vals1.iter() {
+                                session.give(result(&key, val1, &val2));
+                            }
+                        }
+                    }
+                    state2.entry(time2).or_default().entry(key).or_default().push(val2);
+                }
+            });
+
+            // discard values that no future record of the other input can match.
+            let frontier1 = input1.frontier();
+            let frontier2 = input2.frontier();
+            while let Some(time1) = state1.keys().next().cloned().filter(|t| !frontier2.less_equal(&t.saturating_add(within))) {
+                state1.remove(&time1);
+            }
+            while let Some(time2) = state2.keys().next().cloned().filter(|t| !frontier1.less_equal(&t.saturating_add(within))) {
+                state2.remove(&time2);
+            }
+        })
+    }
+}
+
+/// Groups the values of two keyed streams by key within each timestamp.
+pub trait CoGroup<G: Scope, K: ExchangeData+Hash+Eq, V1: ExchangeData> {
+    /// Groups records of the form `(key, val1)` and `(key, val2)` with equal keys and times.
+    ///
+    /// Both inputs are exchanged by `hash` applied to their keys. Once a time is complete on
+    /// both inputs, `logic` is called for each key with values at that time, with the values of
+    /// each input, either of which may be empty, and a vector in which to place results. This
+    /// can express inner, outer, and anti joins, among other per-key computations.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, CoGroup, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     let names = vec![(0, 'a'), (1, 'b')].to_stream(scope);
+    ///     vec![(0, 10), (0, 11), (2, 12)]
+    ///         .to_stream(scope)
+    ///         // a full outer join, counting the values of each input.
+    ///         .cogroup(&names, |key| *key as u64, |key, vals, names, output| {
+    ///             output.push((*key, vals.len(), names.len()));
+    ///         })
+    ///         .capture()
+    /// });
+    ///
+    /// let mut extracted = captured.extract();
+    /// extracted[0].1.sort();
+    /// assert_eq!(extracted[0].1, vec![(0, 2, 1), (1, 0, 1), (2, 1, 0)]);
+    /// ```
+    fn cogroup<V2, R, H, F>(&self, other: &Stream<G, (K, V2)>, hash: H, logic: F) -> Stream<G, R>
+    where
+        V2: ExchangeData,
+        R: Data,
+        H: Fn(&K)->u64+'static,
+        F: FnMut(&K, &[V1], &[V2], &mut Vec<R>)+'static;
+}
+
+impl<G: Scope, K: ExchangeData+Hash+Eq, V1: ExchangeData> CoGroup<G, K, V1> for Stream<G, (K, V1)> {
+
+    fn cogroup<V2, R, H, F>(&self, other: &Stream<G, (K, V2)>, hash: H, mut logic: F) -> Stream<G, R>
+    where
+        V2: ExchangeData,
+        R: Data,
+        H: Fn(&K)->u64+'static,
+        F: FnMut(&K, &[V1], &[V2], &mut Vec<R>)+'static {
+
+        let hash1 = Rc::new(hash);
+        let hash2 = hash1.clone();
+        let exchange1 = Exchange::new(move |x: &(K, V1)| hash1(&x.0));
+        let exchange2 = Exchange::new(move |x: &(K, V2)| hash2(&x.0));
+
+        // per-time maps from keys to the values received on both inputs.
+        let mut state = HashMap::<G::Timestamp, HashMap<K, (Vec<V1>, Vec<V2>)>>::new();
+
+        let mut vector1 = Vec::new();
+        let mut vector2 = Vec::new();
+        let mut results = Vec::new();
+
+        self.binary_notify(other, exchange1, exchange2, "CoGroup", vec![], move |input1, input2, output, notificator| {
+
+            input1.for_each(|time, data| {
+                data.swap(&mut vector1);
+                let groups = state.entry(time.time().clone()).or_default();
+                for (key, val1) in vector1.drain(..) {
+                    groups.entry(key).or_default().0.push(val1);
+                }
+                notificator.notify_at(time.retain());
+            });
+
+            input2.for_each(|time, data| {
+                data.swap(&mut vector2);
+                let groups = state.entry(time.time().clone()).or_default();
+                for (key, val2) in vector2.drain(..) {
+                    groups.entry(key).or_default().1.push(val2);
+                }
+                notificator.notify_at(time.retain());
+            });
+
+            // present the groups of completed times.
+            notificator.for_each(|time, _, _| {
+                if let Some(groups) = state.remove(time.time()) {
+                    let mut session = output.session(&time);
+                    for (key, (vals1, vals2)) in groups {
+                        logic(&key, &vals1[..], &vals2[..], &mut results);
+                        session.give_vec(&mut results);
+                    }
+                }
+            });
+        })
+    }
+}
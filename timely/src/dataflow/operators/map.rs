@@ -22,6 +22,9 @@ pub trait Map<S: Scope, D: Data> {
     fn map<D2: Data, L: FnMut(D)->D2+'static>(&self, logic: L) -> Stream<S, D2>;
     /// Updates each element of the stream and yields the element, re-using memory where possible.
     ///
+    /// Each batch of records is updated where it sits, and the same allocation is then sent
+    /// downstream, rather than moving each record into a newly allocated buffer.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::operators::{ToStream, Map, Inspect};
@@ -46,6 +49,22 @@ pub trait Map<S: Scope, D: Data> {
     /// });
     /// ```
     fn flat_map<I: IntoIterator, L: FnMut(D)->I+'static>(&self, logic: L) -> Stream<S, I::Item> where I::Item: Data;
+    /// Consumes each element of the stream and yields at most one new element.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .filter_map(|x| if x % 3 == 0 { Some(x * 10) } else { None })
+    ///            .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![0, 30, 60, 90])]);
+    /// ```
+    fn filter_map<D2: Data, L: FnMut(D)->Option<D2>+'static>(&self, logic: L) -> Stream<S, D2>;
 }
 
 impl<S: Scope, D: Data> Map<S, D> for Stream<S, D> {
@@ -80,4 +99,13 @@ impl<S: Scope, D: Data> Map<S, D> for Stream<S, D> {
             });
         })
     }
+    fn filter_map<D2: Data, L: FnMut(D)->Option<D2>+'static>(&self, mut logic: L) -> Stream<S, D2> {
+        let mut vector = Vec::new();
+        self.unary(Pipeline, "FilterMap", move |_,_| move |input, output| {
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                output.session(&time).give_iterator(vector.drain(..).filter_map(&mut logic));
+            });
+        })
+    }
 }
@@ -12,25 +12,42 @@ pub use self::enterleave::{Enter, EnterAt, Leave};
 // pub use self::queue::*;
 pub use self::input::Input;
 pub use self::unordered_input::UnorderedInput;
-pub use self::feedback::{Feedback, LoopVariable, ConnectLoop};
+pub use self::feedback::{Feedback, LoopVariable, ConnectLoop, Iterate};
 pub use self::concat::{Concat, Concatenate};
 pub use self::partition::Partition;
 pub use self::map::Map;
 pub use self::inspect::Inspect;
 pub use self::filter::Filter;
 pub use self::delay::Delay;
-pub use self::exchange::Exchange;
+pub use self::exchange::{Exchange, PreHashed};
 pub use self::broadcast::Broadcast;
 pub use self::probe::Probe;
 pub use self::to_stream::ToStream;
 pub use self::capture::Capture;
-pub use self::branch::{Branch, BranchWhen};
+pub use self::branch::{Branch, BranchWhen, OkErr, Late};
 
 pub use self::generic::Operator;
 pub use self::generic::{Notificator, FrontierNotificator};
 
 pub use self::reclock::Reclock;
 pub use self::count::Accumulate;
+pub use self::join::{Join, IntervalJoin, CoGroup};
+pub use self::zip::Zip;
+pub use self::sort::Sort;
+pub use self::sketch::Summarize;
+pub use self::control::WithControl;
+pub use self::tick::Tick;
+pub use self::distinct::Distinct;
+pub use self::topk::TopK;
+pub use self::sample::Sample;
+pub use self::window::{Window, Sessions};
+pub use self::stats::Stats;
+pub use self::to_channel::ToChannel;
+pub use self::sink::SinkInto;
+pub use self::files::{ReadLines, WriteFiles};
+pub use self::barrier::Barrier;
+#[cfg(feature = "futures")]
+pub use self::async_stream::{ToStreamAsync, SinkAsync, EnrichAsync};
 
 pub mod enterleave;
 pub mod input;
@@ -55,6 +72,23 @@ pub mod generic;
 
 pub mod reclock;
 pub mod count;
+pub mod join;
+pub mod zip;
+pub mod sort;
+pub mod sketch;
+pub mod control;
+pub mod tick;
+pub mod distinct;
+pub mod topk;
+pub mod sample;
+pub mod window;
+pub mod stats;
+pub mod to_channel;
+pub mod sink;
+pub mod files;
+pub mod barrier;
+#[cfg(feature = "futures")]
+pub mod async_stream;
 
 // keep "mint" module-private
 mod capability;
@@ -22,6 +22,26 @@ pub trait Partition<G: Scope, D: Data, D2: Data, F: Fn(D)->(u64, D2)> {
     ///     streams[2].inspect(|x| println!("seen 2: {:?}", x));
     /// });
     /// ```
+    ///
+    /// All outputs are ports of a single operator, and each record is routed to exactly one.
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Partition, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .partition(3, |x| (x % 3, x * 10))
+    ///            .iter()
+    ///            .map(|stream| stream.capture())
+    ///            .collect::<Vec<_>>()
+    /// });
+    ///
+    /// let extracted = captured.into_iter().map(|c| c.extract()).collect::<Vec<_>>();
+    /// assert_eq!(extracted[0], vec![(0, vec![0, 30, 60, 90])]);
+    /// assert_eq!(extracted[1], vec![(0, vec![10, 40, 70])]);
+    /// assert_eq!(extracted[2], vec![(0, vec![20, 50, 80])]);
+    /// ```
     fn partition(&self, parts: u64, route: F) -> Vec<Stream<G, D2>>;
 }
 
@@ -77,6 +77,41 @@ pub trait Probe<G: Scope, D: Data> {
     /// }).unwrap();
     /// ```
     fn probe_with(&self, handle: &mut Handle<G::Timestamp>) -> Stream<G, D>;
+
+    /// Inserts a progress probe in a stream, which calls `observer` with the frontier of the
+    /// stream each time it changes.
+    ///
+    /// This allows code outside the dataflow to react to progress as it happens, rather than by
+    /// polling a probe handle.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::rc::Rc;
+    /// use std::cell::RefCell;
+    /// use timely::dataflow::operators::{Input, Probe};
+    ///
+    /// timely::execute_directly(|worker| {
+    ///
+    ///     let frontiers = Rc::new(RefCell::new(Vec::new()));
+    ///     let frontiers2 = frontiers.clone();
+    ///     let mut input = worker.dataflow(|scope| {
+    ///         let (input, stream) = scope.new_input::<u64>();
+    ///         stream.frontier_probe(move |frontier| frontiers2.borrow_mut().push(frontier.to_vec()));
+    ///         input
+    ///     });
+    ///
+    ///     for round in 0 .. 3 {
+    ///         input.send(round);
+    ///         input.advance_to(round + 1);
+    ///         worker.step();
+    ///     }
+    ///     input.close();
+    ///     while worker.step() { }
+    ///
+    ///     assert_eq!(*frontiers.borrow(), vec![vec![0], vec![1], vec![2], vec![3], vec![]]);
+    /// });
+    /// ```
+    fn frontier_probe<F: FnMut(AntichainRef<G::Timestamp>)+'static>(&self, observer: F) -> Stream<G, D>;
 }
 
 impl<G: Scope, D: Data> Probe<G, D> for Stream<G, D> {
@@ -88,65 +123,90 @@ impl<G: Scope, D: Data> Probe<G, D> for Stream<G, D> {
         handle
     }
     fn probe_with(&self, handle: &mut Handle<G::Timestamp>) -> Stream<G, D> {
+        probe_core(self, handle, |_| { })
+    }
+    fn frontier_probe<F: FnMut(AntichainRef<G::Timestamp>)+'static>(&self, observer: F) -> Stream<G, D> {
+        probe_core(self, &mut Handle::new(), observer)
+    }
+}
 
-        let mut builder = OperatorBuilder::new("Probe".to_owned(), self.scope());
-        let mut input = PullCounter::new(builder.new_input(self, Pipeline));
-        let (tee, stream) = builder.new_output();
-        let mut output = PushBuffer::new(PushCounter::new(tee));
-
-        let shared_frontier = handle.frontier.clone();
-        let mut started = false;
-
-        let mut vector = Vec::new();
-
-        builder.build(
-            move |progress| {
-
-                // surface all frontier changes to the shared frontier.
-                let mut borrow = shared_frontier.borrow_mut();
-                borrow.update_iter(progress.frontiers[0].drain());
+/// Inserts a probe reporting frontier changes to `handle`, and calling `observer` with the new frontier.
+fn probe_core<G, D, F>(stream: &Stream<G, D>, handle: &mut Handle<G::Timestamp>, mut observer: F) -> Stream<G, D>
+where
+    G: Scope,
+    D: Data,
+    F: FnMut(AntichainRef<G::Timestamp>)+'static,
+{
+    let mut builder = OperatorBuilder::new("Probe".to_owned(), stream.scope());
+    let mut input = PullCounter::new(builder.new_input(stream, Pipeline));
+    let (tee, stream) = builder.new_output();
+    let mut output = PushBuffer::new(PushCounter::new(tee));
+
+    let shared_frontier = handle.frontier.clone();
+    let mut started = false;
+
+    let mut vector = Vec::new();
+
+    builder.build(
+        move |progress| {
+
+            // surface all frontier changes to the shared frontier.
+            let mut borrow = shared_frontier.borrow_mut();
+            if borrow.update_iter(progress.frontiers[0].drain()).next().is_some() {
+                observer(borrow.frontier());
+            }
 
-                if !started {
-                    // discard initial capability.
-                    progress.internals[0].update(G::Timestamp::minimum(), -1);
-                    started = true;
-                }
+            if !started {
+                // discard initial capability.
+                progress.internals[0].update(G::Timestamp::minimum(), -1);
+                started = true;
+            }
 
-                use crate::communication::message::RefOrMut;
+            use crate::communication::message::RefOrMut;
 
-                while let Some(message) = input.next() {
-                    let (time, data) = match message.as_ref_or_mut() {
-                        RefOrMut::Ref(reference) => (&reference.time, RefOrMut::Ref(&reference.data)),
-                        RefOrMut::Mut(reference) => (&reference.time, RefOrMut::Mut(&mut reference.data)),
-                    };
-                    data.swap(&mut vector);
-                    output.session(time).give_vec(&mut vector);
-                }
-                output.cease();
+            while let Some(message) = input.next() {
+                let (time, data) = match message.as_ref_or_mut() {
+                    RefOrMut::Ref(reference) => (&reference.time, RefOrMut::Ref(&reference.data)),
+                    RefOrMut::Mut(reference) => (&reference.time, RefOrMut::Mut(&mut reference.data)),
+                };
+                data.swap(&mut vector);
+                output.session(time).give_vec(&mut vector);
+            }
+            output.cease();
 
-                // extract what we know about progress from the input and output adapters.
-                input.consumed().borrow_mut().drain_into(&mut progress.consumeds[0]);
-                output.inner().produced().borrow_mut().drain_into(&mut progress.produceds[0]);
+            // extract what we know about progress from the input and output adapters.
+            input.consumed().borrow_mut().drain_into(&mut progress.consumeds[0]);
+            output.inner().produced().borrow_mut().drain_into(&mut progress.produceds[0]);
 
-                false
-            },
-        );
+            false
+        },
+    );
 
-        stream
-    }
+    stream
 }
 
 /// Reports information about progress at the probe.
+///
+/// The handle reflects the frontier of the probed stream: the times at which records may still
+/// arrive at the probe. Driver code can use it to wait until the dataflow has caught up with its
+/// inputs, for example by stepping the worker while `less_than(input.time())` holds, before
+/// introducing more input.
 pub struct Handle<T:Timestamp> {
     frontier: Rc<RefCell<MutableAntichain<T>>>
 }
 
 impl<T: Timestamp> Handle<T> {
     /// returns true iff the frontier is strictly less than `time`.
+    ///
+    /// When this returns false, all times strictly less than `time` have completed at the probe.
     #[inline] pub fn less_than(&self, time: &T) -> bool { self.frontier.borrow().less_than(time) }
     /// returns true iff the frontier is less than or equal to `time`.
+    ///
+    /// When this returns false, `time` and all times less than it have completed at the probe.
     #[inline] pub fn less_equal(&self, time: &T) -> bool { self.frontier.borrow().less_equal(time) }
     /// returns true iff the frontier is empty.
+    ///
+    /// An empty frontier means that no further records will arrive at the probe at any time.
     #[inline] pub fn done(&self) -> bool { self.frontier.borrow().is_empty() }
     /// Allocates a new handle.
     #[inline] pub fn new() -> Self { Handle { frontier: Rc::new(RefCell::new(MutableAntichain::new())) } }
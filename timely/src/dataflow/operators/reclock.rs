@@ -1,4 +1,4 @@
-//! Extension methods for `Stream` based on record-by-record transformation.
+//! Extension methods for `Stream` that re-time records according to another stream.
 
 use crate::Data;
 use crate::order::PartialOrder;
@@ -0,0 +1,175 @@
+//! Uniform random samples of the records at each time.
+use std::hash::{Hash, Hasher};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+
+use crate::ExchangeData;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::channels::pact::{Pipeline, Exchange, ParallelizationContract};
+use crate::dataflow::operators::{Map, TopK};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Uniform random samples of the records at each time.
+///
+/// Each record is assigned a random priority by hashing `seed` together with its time, the
+/// record itself, and the number of identical records seen before it at that time, so that
+/// identical records are sampled independently. Repeated runs with the same seed and inputs
+/// produce the same samples, however records are ordered within batches.
+pub trait Sample<G: Scope, D: ExchangeData+Hash> {
+    /// Produces a uniform sample of `n` records at each time, without replacement.
+    ///
+    /// Records are exchanged so that identical records meet at one worker, and the `n` records
+    /// with the least priorities are retained, first at each worker and then at worker zero. The sample is produced once its time is complete. If fewer than `n` records
+    /// exist at a time, all of them are produced.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Sample, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..100).to_stream(scope)
+    ///             .sample(5, 0)
+    ///             .capture()
+    /// });
+    ///
+    /// let extracted = captured.extract();
+    /// assert_eq!(extracted.len(), 1);
+    /// assert_eq!(extracted[0].1.len(), 5);
+    /// assert!(extracted[0].1.windows(2).all(|x| x[0] < x[1] && x[1] < 100));
+    /// ```
+    ///
+    /// Samples are drawn from the records of all workers, and do not depend on how the records
+    /// are distributed among them.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Delay, Sample, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let run = |workers| {
+    ///     let (send, recv) = ::std::sync::mpsc::channel();
+    ///     let send = Arc::new(Mutex::new(send));
+    ///     timely::execute(timely::Configuration::Process(workers), move |worker| {
+    ///         let send = send.lock().unwrap().clone();
+    ///         let (index, peers) = (worker.index() as u64, worker.peers() as u64);
+    ///         worker.dataflow::<u64,_,_>(move |scope| {
+    ///             (0..1000u64).filter(move |x| x % peers == index)
+    ///                         .to_stream(scope)
+    ///                         .delay(|x, _time| x % 3)
+    ///                         .sample(10, 7)
+    ///                         .capture_into(send);
+    ///         });
+    ///     }).unwrap();
+    ///     recv.extract()
+    /// };
+    ///
+    /// let extracted = run(2);
+    /// assert_eq!(extracted.iter().map(|x| x.0).collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// assert!(extracted.iter().all(|(time, data)| data.len() == 10 && data.iter().all(|x| x % 3 == *time)));
+    /// assert_eq!(extracted, run(1));
+    /// assert_eq!(extracted, run(3));
+    /// ```
+    fn sample(&self, n: usize, seed: u64) -> Stream<G, D>;
+
+    /// Produces each record independently with probability `p`.
+    ///
+    /// Records are sampled where they are, without exchange or waiting for times to complete.
+    /// Identical records at different workers are told apart by the worker index, so the sample
+    /// is reproduced when records are distributed among workers in the same way.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Sample, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let run = |seed| {
+    ///     timely::example(move |scope| {
+    ///         (0..1000).to_stream(scope)
+    ///                  .sample_ratio(0.1, seed)
+    ///                  .capture()
+    ///     }).extract()
+    /// };
+    ///
+    /// let extracted = run(0);
+    /// assert!(50 < extracted[0].1.len() && extracted[0].1.len() < 150);
+    /// assert_eq!(extracted, run(0));
+    /// assert_ne!(extracted, run(1));
+    ///
+    /// // copies of one record are sampled independently.
+    /// let copies = timely::example(|scope| {
+    ///     (0..1000).map(|_| 7).to_stream(scope)
+    ///              .sample_ratio(0.1, 0)
+    ///              .capture()
+    /// }).extract();
+    /// assert!(50 < copies[0].1.len() && copies[0].1.len() < 150);
+    /// ```
+    fn sample_ratio(&self, p: f64, seed: u64) -> Stream<G, D>;
+}
+
+impl<G: Scope, D: ExchangeData+Hash> Sample<G, D> for Stream<G, D> {
+
+    fn sample(&self, n: usize, seed: u64) -> Stream<G, D> {
+        let pact = Exchange::new(|datum: &D| hashed(datum));
+        with_priority(self, pact, seed, 0, |priority, datum| Some((priority, datum)))
+            .topk(n, |x: &(u64, D), y: &(u64, D)| y.0.cmp(&x.0))
+            .map(|(_priority, datum)| datum)
+    }
+
+    fn sample_ratio(&self, p: f64, seed: u64) -> Stream<G, D> {
+        let index = self.scope().index() as u64;
+        with_priority(self, Pipeline, seed, index, move |priority, datum| {
+            // a value uniformly distributed in `[0, 1)`.
+            let uniform = (priority >> 11) as f64 / (1u64 << 53) as f64;
+            if uniform < p { Some(datum) } else { None }
+        })
+    }
+}
+
+/// Applies `logic` to each record and its priority, after exchanging with `pact`.
+///
+/// The priority is a hash of `seed`, `salt`, the time, the record, and the number of identical
+/// records this operator has seen before it at that time.
+fn with_priority<G, D, D2, P, L>(stream: &Stream<G, D>, pact: P, seed: u64, salt: u64, mut logic: L) -> Stream<G, D2>
+where
+    G: Scope,
+    D: ExchangeData+Hash,
+    D2: ExchangeData,
+    P: ParallelizationContract<G::Timestamp, D>,
+    L: FnMut(u64, D)->Option<D2>+'static,
+{
+    let mut vector = Vec::new();
+    // the number of records seen at each incomplete time, for each record hash.
+    let mut copies = HashMap::new();
+
+    stream.unary_frontier(pact, "Sample", move |_, _| move |input, output| {
+        input.for_each(|time, data| {
+            data.swap(&mut vector);
+            let time_hash = {
+                let mut hasher = DefaultHasher::new();
+                seed.hash(&mut hasher);
+                salt.hash(&mut hasher);
+                time.time().hash(&mut hasher);
+                hasher
+            };
+            output.session(&time).give_iterator(vector.drain(..).filter_map(|datum| {
+                let mut hasher = time_hash.clone();
+                datum.hash(&mut hasher);
+                let copy = copies.entry((time.time().clone(), hashed(&datum))).or_insert(0u64);
+                copy.hash(&mut hasher);
+                *copy += 1;
+                logic(hasher.finish(), datum)
+            }));
+        });
+
+        let frontier = input.frontier();
+        copies.retain(|(time, _), _| frontier.less_equal(time));
+    })
+}
+
+/// The hash of a record on its own.
+fn hashed<D: Hash>(datum: &D) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    datum.hash(&mut hasher);
+    hasher.finish()
+}
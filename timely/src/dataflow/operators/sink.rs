@@ -0,0 +1,98 @@
+//! Delivery of a `Stream` to an external store, committed as its times complete.
+
+use crate::Data;
+use crate::progress::frontier::AntichainRef;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// A destination for records which are written as they arrive and committed as times complete.
+///
+/// A store that supports transactions, or whose writes are idempotent, can use these calls to
+/// deliver each record exactly once: writes are staged, and a commit makes visible the writes at
+/// each time that no longer appears in the frontier. A computation restarted from its inputs
+/// repeats writes at times that were not committed, and the store should discard or overwrite
+/// writes that were staged but not committed.
+pub trait Sink<T, D> {
+    /// Stages a batch of records at `time`, which may be drained.
+    fn write(&mut self, time: &T, batch: &mut Vec<D>);
+    /// Commits the writes at each time not greater or equal to an element of `frontier`.
+    ///
+    /// Every write at such a time has been staged, and no more will follow. The frontier is
+    /// empty once the stream is complete.
+    fn commit(&mut self, frontier: AntichainRef<T>);
+}
+
+/// Delivers a `Stream` to a `Sink`.
+pub trait SinkInto<G: Scope, D: Data> {
+    /// Writes each batch of records to `sink` as it arrives, and commits whenever the input
+    /// frontier advances past the time of some write.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::rc::Rc;
+    /// use std::cell::RefCell;
+    /// use timely::progress::frontier::AntichainRef;
+    /// use timely::dataflow::operators::{ToStream, Delay, SinkInto};
+    /// use timely::dataflow::operators::sink::Sink;
+    ///
+    /// // stages records, and publishes those at times that have completed.
+    /// struct Store {
+    ///     staged: Vec<(u64, u64)>,
+    ///     committed: Rc<RefCell<Vec<(u64, u64)>>>,
+    /// }
+    ///
+    /// impl Sink<u64, u64> for Store {
+    ///     fn write(&mut self, time: &u64, batch: &mut Vec<u64>) {
+    ///         self.staged.extend(batch.drain(..).map(|x| (*time, x)));
+    ///     }
+    ///     fn commit(&mut self, frontier: AntichainRef<u64>) {
+    ///         let mut committed = self.committed.borrow_mut();
+    ///         committed.extend(self.staged.iter().filter(|x| !frontier.less_equal(&x.0)));
+    ///         self.staged.retain(|x| frontier.less_equal(&x.0));
+    ///     }
+    /// }
+    ///
+    /// let committed = timely::execute_directly(|worker| {
+    ///     let committed = Rc::new(RefCell::new(Vec::new()));
+    ///     let store = Store { staged: Vec::new(), committed: committed.clone() };
+    ///     worker.dataflow::<u64,_,_>(|scope| {
+    ///         (0 .. 6).to_stream(scope)
+    ///                 .delay(|x, _time| x / 2)
+    ///                 .sink_into(store);
+    ///     });
+    ///     while worker.step() { }
+    ///     committed.replace(Vec::new())
+    /// });
+    ///
+    /// assert_eq!(committed, vec![(0, 0), (0, 1), (1, 2), (1, 3), (2, 4), (2, 5)]);
+    /// ```
+    fn sink_into<S: Sink<G::Timestamp, D>+'static>(&self, sink: S);
+}
+
+impl<G: Scope, D: Data> SinkInto<G, D> for Stream<G, D> {
+    fn sink_into<S: Sink<G::Timestamp, D>+'static>(&self, mut sink: S) {
+
+        // times of writes that have not yet been committed.
+        let mut pending = Vec::new();
+        let mut vector = Vec::new();
+
+        self.sink(Pipeline, "Sink", move |input| {
+
+            while let Some((time, data)) = input.next() {
+                data.swap(&mut vector);
+                sink.write(time.time(), &mut vector);
+                vector.clear();
+                if !pending.contains(time.time()) {
+                    pending.push(time.time().clone());
+                }
+            }
+
+            let frontier = input.frontier().frontier();
+            if pending.iter().any(|time| !frontier.less_equal(time)) {
+                pending.retain(|time| frontier.less_equal(time));
+                sink.commit(frontier);
+            }
+        });
+    }
+}
@@ -0,0 +1,408 @@
+//! Approximate summaries of numeric streams, merged across workers at each timestamp.
+
+use std::hash::{Hash, Hasher};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+
+use abomonation::Abomonation;
+
+use crate::{Data, ExchangeData};
+use crate::dataflow::channels::pact::{Pipeline, Exchange};
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// A summary of values which can be built at each worker and merged.
+///
+/// Merging two sketches must produce the sketch of the union of their values, so that the
+/// result is independent of how the values were distributed across workers.
+pub trait Sketch: ExchangeData+Clone {
+    /// Adds a value to the sketch.
+    fn insert(&mut self, value: f64);
+    /// Adds the values summarized by `other` to the sketch.
+    fn merge(&mut self, other: Self);
+}
+
+/// Counts of values in fixed buckets.
+///
+/// Bucket `i` counts the values at least boundary `i - 1` and less than boundary `i`, with the
+/// first bucket counting values less than the first boundary and the last bucket counting values
+/// at least the last boundary.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::sketch::{Sketch, Histogram};
+///
+/// let mut histogram = Histogram::new(vec![1.0, 10.0]);
+/// for value in &[0.5, 2.0, 3.0, 10.0, 100.0] {
+///     histogram.insert(*value);
+/// }
+/// assert_eq!(histogram.counts(), &[1, 2, 2]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    boundaries: Vec<f64>,
+    counts: Vec<u64>,
+}
+
+impl Abomonation for Histogram {
+    unsafe fn entomb<W: ::std::io::Write>(&self, write: &mut W) -> ::std::io::Result<()> {
+        self.boundaries.entomb(write)?;
+        self.counts.entomb(write)?;
+        Ok(())
+    }
+    unsafe fn exhume<'b>(&mut self, bytes: &'b mut [u8]) -> Option<&'b mut [u8]> {
+        let bytes = self.boundaries.exhume(bytes)?;
+        let bytes = self.counts.exhume(bytes)?;
+        Some(bytes)
+    }
+    fn extent(&self) -> usize {
+        self.boundaries.extent() + self.counts.extent()
+    }
+}
+
+impl Histogram {
+    /// Allocates an empty histogram from increasing bucket boundaries.
+    pub fn new(boundaries: Vec<f64>) -> Self {
+        assert!(boundaries.windows(2).all(|x| x[0] < x[1]), "histogram boundaries must be increasing");
+        let counts = vec![0; boundaries.len() + 1];
+        Histogram { boundaries, counts }
+    }
+    /// The boundaries of the buckets.
+    pub fn boundaries(&self) -> &[f64] {
+        &self.boundaries[..]
+    }
+    /// The number of values in each bucket.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts[..]
+    }
+}
+
+impl Sketch for Histogram {
+    fn insert(&mut self, value: f64) {
+        let index = self.boundaries.iter().take_while(|boundary| **boundary <= value).count();
+        self.counts[index] += 1;
+    }
+    fn merge(&mut self, other: Self) {
+        assert_eq!(self.boundaries, other.boundaries, "merged histograms must have the same boundaries");
+        for (count, other) in self.counts.iter_mut().zip(other.counts) {
+            *count += other;
+        }
+    }
+}
+
+/// Approximate quantiles of values, with bounded relative error.
+///
+/// Positive values are counted in buckets whose bounds grow geometrically, so that each reported
+/// quantile is within a relative error of `accuracy` of a value at the requested rank. Values
+/// that are zero or negative are counted together, and reported as zero. The size of the sketch
+/// grows with the logarithm of the range of the values, rather than with their number.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::sketch::{Sketch, Quantiles};
+///
+/// let mut sketch = Quantiles::new(0.01);
+/// for value in 1 .. 1001 {
+///     sketch.insert(value as f64);
+/// }
+/// assert_eq!(sketch.count(), 1000);
+/// let median = sketch.quantile(0.5).unwrap();
+/// assert!((median - 500.0).abs() <= 0.01 * 500.0 + 1.0);
+/// let p99 = sketch.quantile(0.99).unwrap();
+/// assert!((p99 - 990.0).abs() <= 0.01 * 990.0 + 1.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Quantiles {
+    accuracy: f64,
+    zeros: u64,
+    // counts of positive values by bucket index, sorted by index.
+    buckets: Vec<(i64, u64)>,
+}
+
+impl Abomonation for Quantiles {
+    unsafe fn entomb<W: ::std::io::Write>(&self, write: &mut W) -> ::std::io::Result<()> {
+        self.buckets.entomb(write)?;
+        Ok(())
+    }
+    unsafe fn exhume<'b>(&mut self, bytes: &'b mut [u8]) -> Option<&'b mut [u8]> {
+        let bytes = self.buckets.exhume(bytes)?;
+        Some(bytes)
+    }
+    fn extent(&self) -> usize {
+        self.buckets.extent()
+    }
+}
+
+impl Quantiles {
+    /// Allocates an empty sketch whose quantiles have relative error at most `accuracy`.
+    pub fn new(accuracy: f64) -> Self {
+        assert!(accuracy > 0.0 && accuracy < 1.0, "accuracy must be in (0, 1)");
+        Quantiles { accuracy, zeros: 0, buckets: Vec::new() }
+    }
+    /// The number of values summarized.
+    pub fn count(&self) -> u64 {
+        self.zeros + self.buckets.iter().map(|x| x.1).sum::<u64>()
+    }
+    /// An approximation of the value at quantile `q`, between zero and one, or `None` if the
+    /// sketch is empty.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        assert!((0.0 ..= 1.0).contains(&q), "quantiles must be in [0, 1]");
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let rank = (q * (count - 1) as f64) as u64;
+        let mut seen = self.zeros;
+        if rank < seen {
+            return Some(0.0);
+        }
+        let gamma = self.gamma();
+        for &(index, bucket) in self.buckets.iter() {
+            seen += bucket;
+            if rank < seen {
+                return Some(2.0 * gamma.powi(index as i32) / (gamma + 1.0));
+            }
+        }
+        unreachable!("rank exceeds the number of values")
+    }
+    fn gamma(&self) -> f64 {
+        (1.0 + self.accuracy) / (1.0 - self.accuracy)
+    }
+    fn add(&mut self, index: i64, count: u64) {
+        match self.buckets.binary_search_by_key(&index, |x| x.0) {
+            Ok(position) => self.buckets[position].1 += count,
+            Err(position) => self.buckets.insert(position, (index, count)),
+        }
+    }
+}
+
+impl Sketch for Quantiles {
+    fn insert(&mut self, value: f64) {
+        if value > 0.0 {
+            let index = (value.ln() / self.gamma().ln()).ceil() as i64;
+            self.add(index, 1);
+        }
+        else {
+            self.zeros += 1;
+        }
+    }
+    fn merge(&mut self, other: Self) {
+        assert!(self.accuracy == other.accuracy, "merged sketches must have the same accuracy");
+        self.zeros += other.zeros;
+        for (index, count) in other.buckets {
+            self.add(index, count);
+        }
+    }
+}
+
+/// An estimate of the number of distinct values, by the HyperLogLog algorithm.
+///
+/// Each value is hashed, and the sketch keeps `2^precision` registers, each the largest number
+/// of leading zero bits seen among the hashes assigned to it. The estimate has a relative
+/// standard error of about `1.04 / sqrt(2^precision)`, using one byte per register however many
+/// values are inserted. Values of other types can be inserted by their hashes with `insert_hash`.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::sketch::{Sketch, HyperLogLog};
+///
+/// let mut sketch = HyperLogLog::new(12);
+/// for value in 0 .. 100_000 {
+///     sketch.insert((value % 10_000) as f64);
+/// }
+/// let estimate = sketch.estimate() as f64;
+/// assert!((estimate - 10_000.0).abs() < 500.0);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HyperLogLog {
+    precision: u32,
+    registers: Vec<u8>,
+}
+
+impl Abomonation for HyperLogLog {
+    unsafe fn entomb<W: ::std::io::Write>(&self, write: &mut W) -> ::std::io::Result<()> {
+        self.registers.entomb(write)?;
+        Ok(())
+    }
+    unsafe fn exhume<'b>(&mut self, bytes: &'b mut [u8]) -> Option<&'b mut [u8]> {
+        let bytes = self.registers.exhume(bytes)?;
+        Some(bytes)
+    }
+    fn extent(&self) -> usize {
+        self.registers.extent()
+    }
+}
+
+impl HyperLogLog {
+    /// Allocates an empty sketch with `2^precision` registers, for precision from 4 to 16.
+    pub fn new(precision: u32) -> Self {
+        assert!((4 ..= 16).contains(&precision), "precision must be in [4, 16]");
+        HyperLogLog { precision, registers: vec![0; 1 << precision] }
+    }
+    /// Adds a value to the sketch by its 64 bit hash.
+    pub fn insert_hash(&mut self, hash: u64) {
+        let index = (hash >> (64 - self.precision)) as usize;
+        let rank = ((hash << self.precision).leading_zeros() + 1).min(64 - self.precision + 1) as u8;
+        if self.registers[index] < rank {
+            self.registers[index] = rank;
+        }
+    }
+    /// An estimate of the number of distinct values inserted.
+    pub fn estimate(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum = self.registers.iter().map(|r| 2f64.powi(-(*r as i32))).sum::<f64>();
+        let estimate = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            // linear counting is more accurate for small numbers of values.
+            (m * (m / zeros as f64).ln()).round() as u64
+        }
+        else {
+            estimate.round() as u64
+        }
+    }
+}
+
+impl Sketch for HyperLogLog {
+    fn insert(&mut self, value: f64) {
+        let mut hasher = DefaultHasher::new();
+        value.to_bits().hash(&mut hasher);
+        self.insert_hash(hasher.finish());
+    }
+    fn merge(&mut self, other: Self) {
+        assert_eq!(self.precision, other.precision, "merged sketches must have the same precision");
+        for (register, other) in self.registers.iter_mut().zip(other.registers) {
+            if *register < other {
+                *register = other;
+            }
+        }
+    }
+}
+
+/// Summarizes the values of a stream at each time.
+pub trait Summarize<G: Scope> {
+    /// Summarizes the values at each time with a sketch, produced at worker zero once the time
+    /// is complete.
+    ///
+    /// Each worker inserts its values at a time into a copy of `empty`, and once the time is
+    /// complete sends the sketch to worker zero, which merges the sketches of all workers. Only
+    /// one sketch per worker and time is exchanged, however many values there are.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Map, Summarize, Capture};
+    /// use timely::dataflow::operators::sketch::Histogram;
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (send, recv) = ::std::sync::mpsc::channel();
+    /// let send = Arc::new(Mutex::new(send));
+    ///
+    /// timely::execute(timely::Configuration::Process(2), move |worker| {
+    ///     let send = send.lock().unwrap().clone();
+    ///     worker.dataflow::<u64,_,_>(move |scope| {
+    ///         (0..10).to_stream(scope)
+    ///                .map(|x| x as f64)
+    ///                .sketch(Histogram::new(vec![5.0]))
+    ///                .map(|histogram| histogram.counts().to_vec())
+    ///                .capture_into(send);
+    ///     });
+    /// }).unwrap();
+    ///
+    /// assert_eq!(recv.extract(), vec![(0, vec![vec![10, 10]])]);
+    /// ```
+    fn sketch<S: Sketch>(&self, empty: S) -> Stream<G, S>;
+
+    /// Counts the values at each time in buckets with the given boundaries.
+    ///
+    /// See `Histogram` for the meaning of the boundaries, and `sketch` for how the counts of
+    /// workers are merged.
+    fn histogram(&self, boundaries: Vec<f64>) -> Stream<G, Histogram> {
+        self.sketch(Histogram::new(boundaries))
+    }
+
+    /// Summarizes the values at each time by approximate quantiles with relative error at most
+    /// `accuracy`.
+    ///
+    /// See `Quantiles` for the guarantees of the sketch, and `sketch` for how the sketches of
+    /// workers are merged.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Delay, Summarize, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..200u64).to_stream(scope)
+    ///                .delay(|x, _time| x / 100)
+    ///                .map(|x| (x % 100 + 1) as f64)
+    ///                .quantiles(0.01)
+    ///                .map(|sketch| sketch.quantile(0.99).unwrap().round() as u64)
+    ///                .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![99]), (1, vec![99])]);
+    /// ```
+    fn quantiles(&self, accuracy: f64) -> Stream<G, Quantiles> {
+        self.sketch(Quantiles::new(accuracy))
+    }
+}
+
+impl<G: Scope> Summarize<G> for Stream<G, f64> {
+    fn sketch<S: Sketch>(&self, empty: S) -> Stream<G, S> {
+        sketch_with(self, empty, |sketch, value| sketch.insert(*value))
+    }
+}
+
+/// Builds a sketch of each worker's records at each time with `insert`, and merges the sketches
+/// of all workers at worker zero.
+pub(crate) fn sketch_with<G, D, S, F>(stream: &Stream<G, D>, empty: S, insert: F) -> Stream<G, S>
+where
+    G: Scope,
+    D: Data,
+    S: Sketch,
+    F: Fn(&mut S, &D)+'static,
+{
+    // build a sketch of each worker's records at each time.
+    let mut sketches = HashMap::new();
+    let prototype = empty.clone();
+    let local = stream.unary_notify(Pipeline, "SketchLocal", vec![], move |input, output, notificator| {
+        input.for_each(|time, data| {
+            let sketch = sketches.entry(time.time().clone()).or_insert_with(|| prototype.clone());
+            for datum in data.iter() {
+                insert(sketch, datum);
+            }
+            notificator.notify_at(time.retain());
+        });
+        notificator.for_each(|time, _, _| {
+            if let Some(sketch) = sketches.remove(time.time()) {
+                output.session(&time).give(sketch);
+            }
+        });
+    });
+
+    // merge the sketches of all workers at worker zero.
+    let mut merged = HashMap::new();
+    let mut vector = Vec::new();
+    local.unary_notify(Exchange::new(|_| 0), "SketchMerge", vec![], move |input, output, notificator| {
+        input.for_each(|time, data| {
+            data.swap(&mut vector);
+            let sketch = merged.entry(time.time().clone()).or_insert_with(|| empty.clone());
+            for other in vector.drain(..) {
+                sketch.merge(other);
+            }
+            notificator.notify_at(time.retain());
+        });
+        notificator.for_each(|time, _, _| {
+            if let Some(sketch) = merged.remove(time.time()) {
+                output.session(&time).give(sketch);
+            }
+        });
+    })
+}
@@ -0,0 +1,129 @@
+//! Sorts the records at each timestamp.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Sorts the records at each timestamp.
+pub trait Sort<G: Scope, D: Data> {
+    /// Produces the records at each time in the order given by `cmp`, once the time is complete.
+    ///
+    /// Records are sorted at each worker; exchange records first if the records of all workers
+    /// at a time should be sorted together. Each batch is sorted as it arrives, and the sorted
+    /// batches are merged as more arrive so that few remain to be merged when the time
+    /// completes, avoiding a single sort of all the records. The sort is stable.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Sort, Capture};
+    /// use timely::dataflow::operators::capture::Event;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     vec![3, 1, 4, 1, 5, 9, 2, 6].to_stream(scope)
+    ///                                 .sorted_by(|x, y| y.cmp(x))
+    ///                                 .capture()
+    /// });
+    ///
+    /// // read the records in the order produced, as `extract` would sort them.
+    /// let produced = captured.try_iter().flat_map(|event| match event {
+    ///     Event::Messages(_time, data) => data,
+    ///     Event::Progress(_) => Vec::new(),
+    /// });
+    /// assert_eq!(produced.collect::<Vec<_>>(), vec![9, 6, 5, 4, 3, 2, 1, 1]);
+    /// ```
+    fn sorted_by<F: Fn(&D, &D)->Ordering+'static>(&self, cmp: F) -> Stream<G, D>;
+
+    /// Produces the records at each time in increasing order, once the time is complete.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Sort, Capture};
+    /// use timely::dataflow::operators::capture::Event;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10).rev()
+    ///            .to_stream(scope)
+    ///            .delay(|x, _time| x % 2)
+    ///            .sorted()
+    ///            .capture()
+    /// });
+    ///
+    /// let produced = captured.try_iter().flat_map(|event| match event {
+    ///     Event::Messages(time, data) => data.into_iter().map(|x| (time, x)).collect(),
+    ///     Event::Progress(_) => Vec::new(),
+    /// });
+    /// assert_eq!(produced.collect::<Vec<_>>(), vec![(0, 0), (0, 2), (0, 4), (0, 6), (0, 8), (1, 1), (1, 3), (1, 5), (1, 7), (1, 9)]);
+    /// ```
+    fn sorted(&self) -> Stream<G, D> where D: Ord {
+        self.sorted_by(|x, y| x.cmp(y))
+    }
+}
+
+impl<G: Scope, D: Data> Sort<G, D> for Stream<G, D> {
+    fn sorted_by<F: Fn(&D, &D)->Ordering+'static>(&self, cmp: F) -> Stream<G, D> {
+
+        // per-time sorted runs, each at most half the length of the run before it.
+        let mut runs = HashMap::<G::Timestamp, Vec<Vec<D>>>::new();
+
+        self.unary_notify(Pipeline, "Sort", vec![], move |input, output, notificator| {
+
+            input.for_each(|time, data| {
+                let mut batch = data.replace(Vec::new());
+                batch.sort_by(|x, y| cmp(x, y));
+                let stack = runs.entry(time.time().clone()).or_default();
+                stack.push(batch);
+                while stack.len() > 1 && stack[stack.len() - 2].len() <= 2 * stack[stack.len() - 1].len() {
+                    let newer = stack.pop().unwrap();
+                    let older = stack.pop().unwrap();
+                    stack.push(merge(older, newer, &cmp));
+                }
+                notificator.notify_at(time.retain());
+            });
+
+            // merge the remaining runs of completed times into the output.
+            notificator.for_each(|time, _, _| {
+                if let Some(stack) = runs.remove(time.time()) {
+                    let mut heads = stack.into_iter().map(|run| run.into_iter()).collect::<Vec<_>>();
+                    let mut session = output.session(&time);
+                    loop {
+                        // the earliest run with a least head, for stability.
+                        let mut least: Option<usize> = None;
+                        for index in 0 .. heads.len() {
+                            if let Some(head) = heads[index].as_slice().first() {
+                                if least.map(|l| cmp(head, &heads[l].as_slice()[0]) == Ordering::Less) != Some(false) {
+                                    least = Some(index);
+                                }
+                            }
+                        }
+                        match least {
+                            Some(index) => session.give(heads[index].next().unwrap()),
+                            None => break,
+                        }
+                    }
+                }
+            });
+        })
+    }
+}
+
+/// Merges two sorted runs, preferring records of `older` among equal records.
+fn merge<D, F: Fn(&D, &D)->Ordering>(older: Vec<D>, newer: Vec<D>, cmp: &F) -> Vec<D> {
+    let mut result = Vec::with_capacity(older.len() + newer.len());
+    let mut older = older.into_iter().peekable();
+    let mut newer = newer.into_iter().peekable();
+    while let (Some(x), Some(y)) = (older.peek(), newer.peek()) {
+        if cmp(y, x) == Ordering::Less {
+            result.push(newer.next().unwrap());
+        }
+        else {
+            result.push(older.next().unwrap());
+        }
+    }
+    result.extend(older);
+    result.extend(newer);
+    result
+}
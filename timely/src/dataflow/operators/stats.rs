@@ -0,0 +1,52 @@
+//! Summarizes the records at each time.
+use std::collections::HashMap;
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Summarizes the records at each time.
+pub trait Stats<G: Scope, D: Data> {
+    /// Produces the number of records and their approximate size in bytes at each time.
+    ///
+    /// Each worker produces `(time, count, bytes)` for the records it observed at `time`, once the
+    /// time is complete. The size of each record is estimated as `std::mem::size_of::<D>()`, which
+    /// does not include any memory the record owns on the heap.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Stats, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10u64).to_stream(scope)
+    ///               .delay(|x, _time| if *x < 3 { 0 } else { 1 })
+    ///               .stats()
+    ///               .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![(0, 3, 24)]), (1, vec![(1, 7, 56)])]);
+    /// ```
+    fn stats(&self) -> Stream<G, (G::Timestamp, usize, usize)>;
+}
+
+impl<G: Scope, D: Data> Stats<G, D> for Stream<G, D> {
+    fn stats(&self) -> Stream<G, (G::Timestamp, usize, usize)> {
+
+        let mut counts = HashMap::new();
+        self.unary_notify(Pipeline, "Stats", vec![], move |input, output, notificator| {
+            input.for_each(|time, data| {
+                *counts.entry(time.time().clone()).or_insert(0) += data.len();
+                notificator.notify_at(time.retain());
+            });
+
+            notificator.for_each(|time, _, _| {
+                if let Some(count) = counts.remove(time.time()) {
+                    let bytes = count * ::std::mem::size_of::<D>();
+                    output.session(&time).give((time.time().clone(), count, bytes));
+                }
+            });
+        })
+    }
+}
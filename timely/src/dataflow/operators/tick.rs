@@ -0,0 +1,83 @@
+//! A source of records at regular intervals of wall-clock time.
+
+use std::time::{Duration, Instant};
+
+use crate::dataflow::operators::generic::operator::source;
+use crate::dataflow::{Stream, Scope};
+
+/// Produces records at regular intervals of wall-clock time.
+pub trait Tick<G: Scope<Timestamp=u64>> {
+    /// Produces tick `n`, at time `n`, once `n` periods have elapsed since the dataflow was
+    /// constructed, for the first `count` ticks or without end if `count` is `None`.
+    ///
+    /// Ticks are produced at every worker if `every_worker` is true, and otherwise only at worker
+    /// zero, though every worker advances its time with the ticks. The operator asks to be
+    /// scheduled when its next tick is due, so a worker stepped with `step_or_park` wakes in time
+    /// to produce it even if the dataflow is otherwise idle. Ticks that fall due while the worker
+    /// is busy are produced together once it schedules the operator.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use timely::dataflow::operators::{Tick, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let start = Instant::now();
+    /// let captured = timely::execute_directly(|worker| {
+    ///     let captured = worker.dataflow(|scope| {
+    ///         scope.tick(Duration::from_millis(10), Some(5), true)
+    ///              .capture()
+    ///     });
+    ///     // parks between ticks, rather than spinning.
+    ///     while worker.step_or_park(None) { }
+    ///     captured
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), (0..5).map(|n| (n, vec![n])).collect::<Vec<_>>());
+    /// assert!(start.elapsed() >= Duration::from_millis(40));
+    /// ```
+    fn tick(&mut self, period: Duration, count: Option<u64>, every_worker: bool) -> Stream<G, u64>;
+}
+
+impl<G: Scope<Timestamp=u64>> Tick<G> for G {
+    fn tick(&mut self, period: Duration, count: Option<u64>, every_worker: bool) -> Stream<G, u64> {
+
+        assert!(period > Duration::from_secs(0), "ticks must be separated by some time");
+
+        let produce = every_worker || self.index() == 0;
+
+        source(self, "Tick", |capability, info| {
+
+            let activator = self.activator_for(&info.address[..]);
+
+            let start = Instant::now();
+            let mut capability = Some(capability);
+            let mut next = 0;
+            let mut due = Duration::from_secs(0);
+
+            move |output| {
+                if let Some(cap) = capability.as_mut() {
+
+                    // produce each tick that has fallen due.
+                    let elapsed = start.elapsed();
+                    while count.map(|count| next < count) != Some(false) && due <= elapsed {
+                        cap.downgrade(&next);
+                        if produce {
+                            output.session(cap).give(next);
+                        }
+                        next += 1;
+                        due += period;
+                    }
+
+                    if count.map(|count| next < count) == Some(false) {
+                        capability = None;
+                    }
+                    else {
+                        cap.downgrade(&next);
+                        activator.activate_after(due - elapsed);
+                    }
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,107 @@
+//! Forwarding of a `Stream` to a `std::sync::mpsc` channel.
+
+use std::sync::mpsc::Sender;
+use std::thread::Thread;
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Forwards the batches of a `Stream` to a `std::sync::mpsc` channel.
+pub trait ToChannel<G: Scope, D: Data> {
+    /// Sends each batch of records to `sender`, as a pair of its time and its records.
+    ///
+    /// Batches are sent as they arrive, and a time may be reported by several batches, in any
+    /// order with respect to other times. Batches are discarded once the receiver is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, ToChannel};
+    ///
+    /// let (send, recv) = ::std::sync::mpsc::channel();
+    /// let send = Arc::new(Mutex::new(send));
+    ///
+    /// timely::execute(timely::Configuration::Process(2), move |worker| {
+    ///     let send = send.lock().unwrap().clone();
+    ///     let index = worker.index();
+    ///     worker.dataflow::<u64,_,_>(move |scope| {
+    ///         (0..5).map(move |x| 5 * index + x)
+    ///               .to_stream(scope)
+    ///               .sink_to_channel(send);
+    ///     });
+    /// }).unwrap();
+    ///
+    /// let mut received = recv.iter().flat_map(|(_time, data)| data).collect::<Vec<_>>();
+    /// received.sort();
+    /// assert_eq!(received, (0..10).collect::<Vec<_>>());
+    /// ```
+    fn sink_to_channel(&self, sender: Sender<(G::Timestamp, Vec<D>)>) {
+        self.sink_to_channel_core(sender, None)
+    }
+
+    /// Sends each batch of records to `sender`, and unparks `thread` after sending.
+    ///
+    /// This allows a thread outside of timely to park while it waits for batches.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, ToChannel};
+    ///
+    /// let (send, recv) = ::std::sync::mpsc::channel();
+    /// let send = Arc::new(Mutex::new(send));
+    ///
+    /// // a consumer that parks until timely unparks it.
+    /// let consumer = std::thread::spawn(move || {
+    ///     let mut received = Vec::new();
+    ///     while received.len() < 10 {
+    ///         std::thread::park();
+    ///         for (_time, data) in recv.try_iter() {
+    ///             received.extend(data);
+    ///         }
+    ///     }
+    ///     received
+    /// });
+    ///
+    /// let thread = consumer.thread().clone();
+    /// timely::execute(timely::Configuration::Thread, move |worker| {
+    ///     let send = send.lock().unwrap().clone();
+    ///     let thread = thread.clone();
+    ///     worker.dataflow::<u64,_,_>(move |scope| {
+    ///         (0..10).to_stream(scope)
+    ///                .sink_to_channel_unpark(send, thread);
+    ///     });
+    /// }).unwrap();
+    ///
+    /// assert_eq!(consumer.join().unwrap(), (0..10).collect::<Vec<_>>());
+    /// ```
+    fn sink_to_channel_unpark(&self, sender: Sender<(G::Timestamp, Vec<D>)>, thread: Thread) {
+        self.sink_to_channel_core(sender, Some(thread))
+    }
+
+    /// Sends each batch of records to `sender`, and unparks `thread`, if supplied, after sending.
+    fn sink_to_channel_core(&self, sender: Sender<(G::Timestamp, Vec<D>)>, thread: Option<Thread>);
+}
+
+impl<G: Scope, D: Data> ToChannel<G, D> for Stream<G, D> {
+    fn sink_to_channel_core(&self, sender: Sender<(G::Timestamp, Vec<D>)>, thread: Option<Thread>) {
+
+        let mut vector = Vec::new();
+        self.sink(Pipeline, "ToChannel", move |input| {
+            let mut sent = false;
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                // NOTE: An Err(x) result just means the receiver is gone. No need to panic.
+                let _ = sender.send((time.time().clone(), ::std::mem::take(&mut vector)));
+                sent = true;
+            });
+            if sent {
+                if let Some(thread) = thread.as_ref() {
+                    thread.unpark();
+                }
+            }
+        });
+    }
+}
@@ -11,6 +11,10 @@ use crate::dataflow::{Stream, Scope};
 pub trait ToStream<T: Timestamp, D: Data> {
     /// Converts to a timely `Stream`.
     ///
+    /// The iterator is consumed lazily. Each time the operator is scheduled it produces a bounded
+    /// number of records and reschedules itself, holding its capability until the iterator is
+    /// exhausted, so large or generated inputs are not materialized all at once.
+    ///
     /// # Examples
     ///
     /// ```
@@ -25,6 +29,32 @@ pub trait ToStream<T: Timestamp, D: Data> {
     ///
     /// assert_eq!(data1.extract(), data2.extract());
     /// ```
+    ///
+    /// Records are produced across several steps of the worker.
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use std::cell::Cell;
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    ///
+    /// timely::execute(timely::Configuration::Thread, |worker| {
+    ///
+    ///     let seen = Rc::new(Cell::new(0));
+    ///     let seen2 = seen.clone();
+    ///
+    ///     worker.dataflow::<u64,_,_>(|scope| {
+    ///         (0..1_000_000u64)
+    ///             .to_stream(scope)
+    ///             .inspect(move |_| seen2.set(seen2.get() + 1));
+    ///     });
+    ///
+    ///     worker.step();
+    ///     assert!(seen.get() < 1_000_000);
+    ///
+    ///     while worker.step() { }
+    ///     assert_eq!(seen.get(), 1_000_000);
+    /// }).unwrap();
+    /// ```
     fn to_stream<S: Scope<Timestamp=T>>(self, scope: &mut S) -> Stream<S, D>;
 }
 
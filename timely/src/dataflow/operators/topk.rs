@@ -0,0 +1,104 @@
+//! Retains the largest records at each time.
+use std::rc::Rc;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::ExchangeData;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::channels::pact::{Pipeline, Exchange, ParallelizationContract};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Retains the largest records at each time.
+pub trait TopK<G: Scope, D: ExchangeData> {
+    /// Produces the `k` largest records at each time, as ordered by `cmp`.
+    ///
+    /// The reduction happens in two phases: each worker first retains only its own `k`
+    /// largest records for each time, and these candidates are then sent to worker zero,
+    /// which produces the overall `k` largest records once the time is complete. Records
+    /// that compare equal may be retained in any order.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, TopK, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     vec![5, 1, 9, 3, 7, 2, 8].into_iter()
+    ///         .to_stream(scope)
+    ///         .topk(3, |x, y| x.cmp(y))
+    ///         .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![(0, vec![7, 8, 9])]);
+    /// ```
+    ///
+    /// The comparator determines which records are largest, and the result is the same
+    /// regardless of the number of workers.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Delay, TopK, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (send, recv) = ::std::sync::mpsc::channel();
+    /// let send = Arc::new(Mutex::new(send));
+    ///
+    /// timely::execute(timely::Configuration::Process(3), move |worker| {
+    ///     let send = send.lock().unwrap().clone();
+    ///     let index = worker.index() as u64;
+    ///     worker.dataflow::<u64,_,_>(move |scope| {
+    ///         (0..100u64).filter(move |x| x % 3 == index)
+    ///                    .to_stream(scope)
+    ///                    .delay(|x, _time| x % 2)
+    ///                    .topk(2, |x, y| y.cmp(x))
+    ///                    .capture_into(send);
+    ///     });
+    /// }).unwrap();
+    ///
+    /// assert_eq!(recv.extract(), vec![(0, vec![0, 2]), (1, vec![1, 3])]);
+    /// ```
+    fn topk<F: Fn(&D, &D)->Ordering+'static>(&self, k: usize, cmp: F) -> Stream<G, D>;
+}
+
+impl<G: Scope, D: ExchangeData> TopK<G, D> for Stream<G, D> {
+    fn topk<F: Fn(&D, &D)->Ordering+'static>(&self, k: usize, cmp: F) -> Stream<G, D> {
+        let cmp = Rc::new(cmp);
+        let local = retain_largest(self, Pipeline, "TopKLocal", k, cmp.clone());
+        retain_largest(&local, Exchange::new(|_| 0), "TopKGlobal", k, cmp)
+    }
+}
+
+/// Produces the `k` largest records at each complete time, after exchanging with `pact`.
+fn retain_largest<G, D, P, F>(stream: &Stream<G, D>, pact: P, name: &str, k: usize, cmp: Rc<F>) -> Stream<G, D>
+where
+    G: Scope,
+    D: ExchangeData,
+    P: ParallelizationContract<G::Timestamp, D>,
+    F: Fn(&D, &D)->Ordering+'static,
+{
+    let mut stash = HashMap::new();
+    let mut vector = Vec::new();
+
+    stream.unary_notify(pact, name, vec![], move |input, output, notificator| {
+
+        input.for_each(|time, data| {
+            data.swap(&mut vector);
+            let retained = stash.entry(time.time().clone()).or_insert_with(Vec::new);
+            retained.append(&mut vector);
+            // amortize the cost of sorting by allowing the retained records to grow to `2 * k`.
+            if retained.len() > 2 * k {
+                retained.sort_by(|x, y| cmp(y, x));
+                retained.truncate(k);
+            }
+            notificator.notify_at(time.retain());
+        });
+
+        notificator.for_each(|time, _, _| {
+            if let Some(mut retained) = stash.remove(time.time()) {
+                retained.sort_by(|x, y| cmp(y, x));
+                retained.truncate(k);
+                output.session(&time).give_vec(&mut retained);
+            }
+        });
+    })
+}
@@ -74,6 +74,56 @@ pub trait UnorderedInput<G: Scope> {
     ///     assert_eq!(extract[i], (i, vec![i]));
     /// }
     /// ```
+    ///
+    /// Several epochs may be open at once, and data can be introduced at them in any order. An
+    /// epoch completes once no capability remains at or before it.
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use timely::*;
+    /// use timely::dataflow::operators::*;
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let (send, recv) = ::std::sync::mpsc::channel();
+    /// let send = Arc::new(Mutex::new(send));
+    ///
+    /// timely::execute(Configuration::Thread, move |worker| {
+    ///
+    ///     let send = send.lock().unwrap().clone();
+    ///
+    ///     let ((mut input, cap), probe) = worker.dataflow::<usize,_,_>(|scope| {
+    ///         let (input, stream) = scope.new_unordered_input();
+    ///         stream.capture_into(send);
+    ///         (input, stream.probe())
+    ///     });
+    ///
+    ///     // hold capabilities for epochs 0, 1, and 2, out of order.
+    ///     let cap2 = cap.delayed(&2);
+    ///     let cap1 = cap.delayed(&1);
+    ///     let cap0 = cap;
+    ///
+    ///     input.session(cap2.clone()).give('c');
+    ///     input.session(cap0.clone()).give('a');
+    ///     input.session(cap1.clone()).give('b');
+    ///
+    ///     // releasing epoch 1 first does not complete it, as epoch 0 is still open.
+    ///     drop(cap1);
+    ///     for _ in 0..10 { worker.step(); }
+    ///     assert!(probe.less_equal(&0));
+    ///
+    ///     // releasing epoch 0 completes epochs 0 and 1, but not epoch 2.
+    ///     drop(cap0);
+    ///     worker.step_while(|| probe.less_equal(&1));
+    ///     assert!(probe.less_equal(&2));
+    ///
+    ///     drop(cap2);
+    ///     while worker.step() { }
+    ///     assert!(probe.done());
+    /// }).unwrap();
+    ///
+    /// assert_eq!(recv.extract(), vec![(0, vec!['a']), (1, vec!['b']), (2, vec!['c'])]);
+    /// ```
     fn new_unordered_input<D:Data>(&mut self) -> ((UnorderedHandle<G::Timestamp, D>, ActivateCapability<G::Timestamp>), Stream<G, D>);
 }
 
@@ -0,0 +1,186 @@
+//! Groups records into windows of timestamps.
+
+use std::hash::Hash;
+use std::collections::{HashMap, BinaryHeap};
+use std::cmp::Reverse;
+
+use crate::{Data, ExchangeData};
+use crate::dataflow::channels::pact::{Pipeline, Exchange};
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Groups records into windows of timestamps.
+///
+/// A window covers the timestamps from its start (inclusive) to its end (exclusive), and windows
+/// start at multiples of their slide. The records of each window are produced as a single batch
+/// at the window's end, once the input frontier has reached that end. Windows are formed
+/// independently at each worker; exchange records first if windows should collect records from
+/// all workers.
+pub trait Window<G: Scope<Timestamp=u64>, D: Data> {
+    /// Groups records into consecutive, non-overlapping windows of `size` timestamps.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Window, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .delay(|x, _time| *x)
+    ///            .tumbling(4)
+    ///            .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![
+    ///     (4, vec![vec![0, 1, 2, 3]]),
+    ///     (8, vec![vec![4, 5, 6, 7]]),
+    ///     (12, vec![vec![8, 9]]),
+    /// ]);
+    /// ```
+    fn tumbling(&self, size: u64) -> Stream<G, Vec<D>> {
+        self.sliding(size, size)
+    }
+
+    /// Groups records into windows of `size` timestamps, starting every `slide` timestamps.
+    ///
+    /// Each record is produced in every window that contains its timestamp; when `slide` is
+    /// less than `size` windows overlap, and when it is greater some timestamps are in no
+    /// window at all.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Window, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..6).to_stream(scope)
+    ///           .delay(|x, _time| *x)
+    ///           .sliding(4, 2)
+    ///           .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![
+    ///     (4, vec![vec![0, 1, 2, 3]]),
+    ///     (6, vec![vec![2, 3, 4, 5]]),
+    ///     (8, vec![vec![4, 5]]),
+    /// ]);
+    /// ```
+    fn sliding(&self, size: u64, slide: u64) -> Stream<G, Vec<D>>;
+}
+
+impl<G: Scope<Timestamp=u64>, D: Data> Window<G, D> for Stream<G, D> {
+    fn sliding(&self, size: u64, slide: u64) -> Stream<G, Vec<D>> {
+
+        assert!(size > 0, "windows must contain at least one timestamp");
+        assert!(slide > 0, "windows must advance by at least one timestamp");
+
+        self.unary_frontier(Pipeline, "Window", move |_, _| {
+            // the records and capability of each open window by its end, and a queue of the ends.
+            let mut windows = HashMap::new();
+            let mut ends = BinaryHeap::new();
+            let mut vector = Vec::new();
+            move |input, output| {
+                input.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    let mut start = Some(*time.time() - *time.time() % slide);
+                    while let Some(window_start) = start.filter(|s| s + size > *time.time()) {
+                        let end = window_start + size;
+                        windows.entry(end)
+                               .or_insert_with(|| { ends.push(Reverse(end)); (time.delayed(&end), Vec::new()) })
+                               .1
+                               .extend(vector.iter().cloned());
+                        start = window_start.checked_sub(slide);
+                    }
+                    vector.clear();
+                });
+
+                // send windows whose ends the frontier has reached, in order.
+                while ends.peek().map(|Reverse(end)| !input.frontier().less_equal(&(end - 1))) == Some(true) {
+                    let Reverse(end) = ends.pop().unwrap();
+                    let (cap, window) = windows.remove(&end).unwrap();
+                    output.session(&cap).give(window);
+                }
+            }
+        })
+    }
+}
+
+/// Groups the values of each key into sessions of activity.
+pub trait Sessions<G: Scope<Timestamp=u64>, K: ExchangeData+Hash+Eq, V: ExchangeData> {
+    /// Groups the values of each key into sessions separated by at least `gap` timestamps.
+    ///
+    /// Records are exchanged by `hash` of their key. Values whose timestamps differ by less than
+    /// `gap` belong to the same session, and a session ends once `gap` timestamps have passed
+    /// since its last value. Each session is produced at that time, `last + gap`, once the input
+    /// frontier has reached it, as the key and its values ordered by timestamp.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, Sessions, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     // pairs of key and timestamp.
+    ///     vec![('a', 0), ('a', 1), ('a', 2), ('b', 5), ('a', 10), ('a', 11)]
+    ///         .to_stream(scope)
+    ///         .delay(|x, _time| x.1)
+    ///         .sessions(3, |key| *key as u64)
+    ///         .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract(), vec![
+    ///     (5, vec![('a', vec![0, 1, 2])]),
+    ///     (8, vec![('b', vec![5])]),
+    ///     (14, vec![('a', vec![10, 11])]),
+    /// ]);
+    /// ```
+    fn sessions<H: Fn(&K)->u64+'static>(&self, gap: u64, hash: H) -> Stream<G, (K, Vec<V>)>;
+}
+
+impl<G: Scope<Timestamp=u64>, K: ExchangeData+Hash+Eq, V: ExchangeData> Sessions<G, K, V> for Stream<G, (K, V)> {
+    fn sessions<H: Fn(&K)->u64+'static>(&self, gap: u64, hash: H) -> Stream<G, (K, Vec<V>)> {
+
+        assert!(gap > 0, "sessions must be separated by at least one timestamp");
+
+        self.unary_frontier(Exchange::new(move |x: &(K, V)| hash(&x.0)), "Sessions", move |_, _| {
+            // for each key, a capability for its earliest pending value, and its pending values.
+            let mut pending = HashMap::new();
+            let mut vector = Vec::new();
+            move |input, output| {
+                input.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    for (key, val) in vector.drain(..) {
+                        let (cap, values) = pending.entry(key).or_insert_with(|| (time.delayed(time.time()), Vec::new()));
+                        if time.time() < cap.time() {
+                            *cap = time.delayed(time.time());
+                        }
+                        values.push((*time.time(), val));
+                    }
+                });
+
+                // values arriving later are not before the frontier, so a session whose end the
+                // frontier has reached can no longer be extended.
+                let frontier = input.frontier();
+                pending.retain(|key, (cap, values)| {
+                    values.sort_by_key(|x| x.0);
+                    while !values.is_empty() {
+                        let mut last = 0;
+                        while last + 1 < values.len() && values[last + 1].0 - values[last].0 < gap {
+                            last += 1;
+                        }
+                        let end = values[last].0 + gap;
+                        if frontier.less_equal(&(end - 1)) {
+                            break;
+                        }
+                        let session = values.drain(..= last).map(|x| x.1).collect();
+                        output.session(&cap.delayed(&end)).give((key.clone(), session));
+                    }
+                    if let Some(first) = values.first() {
+                        cap.downgrade(&first.0);
+                    }
+                    !values.is_empty()
+                });
+            }
+        })
+    }
+}
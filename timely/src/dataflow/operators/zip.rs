@@ -0,0 +1,122 @@
+//! Pairs the records of two streams by their position within each timestamp.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Pairs the records of two streams by their position within each timestamp.
+///
+/// The `i`-th record received at a time on one input is paired with the `i`-th record received
+/// at the same time on the other, at each worker. Records are paired as soon as both have
+/// arrived, and the records of the faster input are buffered until then. This re-associates the
+/// results of parallel branches of a pipeline, as long as each branch preserves the number and
+/// order of the records at each worker.
+pub trait Zip<G: Scope, D1: Data> {
+    /// Pairs records by position, and panics once a time completes if the inputs had different
+    /// numbers of records at that time.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Zip, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     let stream = (0..5).to_stream(scope);
+    ///     let squares = stream.map(|x| x * x);
+    ///     stream.zip(&squares)
+    ///           .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract()[0].1, vec![(0, 0), (1, 1), (2, 4), (3, 9), (4, 16)]);
+    /// ```
+    fn zip<D2: Data>(&self, other: &Stream<G, D2>) -> Stream<G, (D1, D2)>;
+
+    /// Pairs records by position, and once a time completes pairs the records of the longer input
+    /// that remain with `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Filter, Zip, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     let stream = (0..4).to_stream(scope);
+    ///     let evens = stream.filter(|x| x % 2 == 0);
+    ///     stream.zip_partial(&evens)
+    ///           .capture()
+    /// });
+    ///
+    /// assert_eq!(captured.extract()[0].1, vec![
+    ///     (Some(0), Some(0)),
+    ///     (Some(1), Some(2)),
+    ///     (Some(2), None),
+    ///     (Some(3), None),
+    /// ]);
+    /// ```
+    fn zip_partial<D2: Data>(&self, other: &Stream<G, D2>) -> Stream<G, (Option<D1>, Option<D2>)>;
+}
+
+impl<G: Scope, D1: Data> Zip<G, D1> for Stream<G, D1> {
+    fn zip<D2: Data>(&self, other: &Stream<G, D2>) -> Stream<G, (D1, D2)> {
+        zip_with(self, other, "Zip", |x1, x2| match (x1, x2) {
+            (Some(x1), Some(x2)) => (x1, x2),
+            _ => panic!("Zip inputs have different numbers of records at a time"),
+        })
+    }
+    fn zip_partial<D2: Data>(&self, other: &Stream<G, D2>) -> Stream<G, (Option<D1>, Option<D2>)> {
+        zip_with(self, other, "ZipPartial", |x1, x2| (x1, x2))
+    }
+}
+
+/// Pairs records by position, and presents pairs and any unpaired records to `pair`.
+fn zip_with<G, D1, D2, R, F>(stream1: &Stream<G, D1>, stream2: &Stream<G, D2>, name: &str, pair: F) -> Stream<G, R>
+where
+    G: Scope,
+    D1: Data,
+    D2: Data,
+    R: Data,
+    F: Fn(Option<D1>, Option<D2>)->R+'static,
+{
+    // per-time queues of records not yet paired.
+    let mut pending = HashMap::<G::Timestamp, (VecDeque<D1>, VecDeque<D2>)>::new();
+
+    let mut vector1 = Vec::new();
+    let mut vector2 = Vec::new();
+
+    stream1.binary_notify(stream2, Pipeline, Pipeline, name, vec![], move |input1, input2, output, notificator| {
+
+        input1.for_each(|time, data| {
+            data.swap(&mut vector1);
+            let queues = pending.entry(time.time().clone()).or_default();
+            queues.0.extend(vector1.drain(..));
+            let mut session = output.session(&time);
+            while !queues.0.is_empty() && !queues.1.is_empty() {
+                session.give(pair(queues.0.pop_front(), queues.1.pop_front()));
+            }
+            notificator.notify_at(time.retain());
+        });
+
+        input2.for_each(|time, data| {
+            data.swap(&mut vector2);
+            let queues = pending.entry(time.time().clone()).or_default();
+            queues.1.extend(vector2.drain(..));
+            let mut session = output.session(&time);
+            while !queues.0.is_empty() && !queues.1.is_empty() {
+                session.give(pair(queues.0.pop_front(), queues.1.pop_front()));
+            }
+            notificator.notify_at(time.retain());
+        });
+
+        // present the records that remain unpaired at completed times.
+        notificator.for_each(|time, _, _| {
+            if let Some((queue1, queue2)) = pending.remove(time.time()) {
+                let mut session = output.session(&time);
+                for x1 in queue1 { session.give(pair(Some(x1), None)); }
+                for x2 in queue2 { session.give(pair(None, Some(x2))); }
+            }
+        });
+    })
+}
@@ -132,6 +132,10 @@ pub trait Scope: ScopeParent {
     /// containing scope. It is used mainly to group regions of a dataflow computation, and
     /// provides some computational benefits by abstracting the specifics of the region.
     ///
+    /// Streams enter and leave a region unchanged: their batches of records are moved across
+    /// the boundary of the region without copying, and keep their timestamps. Operators in a
+    /// region are reported in the logging streams with addresses nested within the region's.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::Scope;
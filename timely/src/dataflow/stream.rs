@@ -4,12 +4,15 @@
 //! operator output. Extension methods on the `Stream` type provide the appearance of higher-level
 //! declarative programming, while constructing a dataflow graph underneath.
 
+use std::any::TypeId;
+
 use crate::progress::{Source, Target};
 
 use crate::communication::Push;
 use crate::dataflow::Scope;
 use crate::dataflow::channels::pushers::tee::TeeHelper;
 use crate::dataflow::channels::Bundle;
+use crate::dataflow::channels::pact::KeyHash;
 
 // use dataflow::scopes::root::loggers::CHANNELS_Q;
 
@@ -25,6 +28,15 @@ pub struct Stream<S: Scope, D> {
     scope: S,
     /// Maintains a list of Push<Bundle<T, D>> interested in the stream's output.
     ports: TeeHelper<S::Timestamp, D>,
+    /// The key by which the stream's records are known to be partitioned among workers.
+    partitioning: Option<Partitioning>,
+}
+
+/// The type of a key function, and the number of workers among which records were routed by it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+struct Partitioning {
+    key: TypeId,
+    peers: usize,
 }
 
 impl<S: Scope, D> Stream<S, D> {
@@ -47,10 +59,27 @@ impl<S: Scope, D> Stream<S, D> {
     }
     /// Allocates a `Stream` from a supplied `Source` name and rendezvous point.
     pub fn new(source: Source, output: TeeHelper<S::Timestamp, D>, scope: S) -> Self {
-        Stream { name: source, ports: output, scope }
+        Stream { name: source, ports: output, scope, partitioning: None }
     }
     /// The name of the stream's source operator.
     pub fn name(&self) -> &Source { &self.name }
     /// The scope immediately containing the stream.
     pub fn scope(&self) -> S { self.scope.clone() }
+    /// Indicates whether the stream is known to be partitioned among workers by the key `K`.
+    ///
+    /// Streams produced by `Exchange::exchange_by` are partitioned by their key, and operators
+    /// whose outputs keep each record at the worker it arrived at can record the same with
+    /// `with_partitioning`.
+    pub fn is_partitioned_by<K: KeyHash<D>>(&self) -> bool {
+        self.partitioning == Some(Partitioning { key: TypeId::of::<K>(), peers: self.scope.peers() })
+    }
+    /// Records that the stream is partitioned among workers by the key `K`.
+    ///
+    /// This asserts that each record is at the worker to which `K` routes it, for the current
+    /// number of workers. Exchanges by the same key, for example with the `KeyedPipeline`
+    /// contract, rely on this to avoid moving records again, and check it in debug builds.
+    pub fn with_partitioning<K: KeyHash<D>>(mut self) -> Self {
+        self.partitioning = Some(Partitioning { key: TypeId::of::<K>(), peers: self.scope.peers() });
+        self
+    }
 }
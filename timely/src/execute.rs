@@ -2,6 +2,7 @@
 
 use crate::communication::{initialize_from, Configuration, Allocator, allocator::AllocateBuilder, WorkerGuards};
 use crate::dataflow::scopes::Child;
+use crate::scheduling::Scheduler;
 use crate::worker::Worker;
 
 /// Executes a single-threaded timely dataflow computation.
@@ -55,6 +56,52 @@ where
     crate::execute::execute_directly(|worker| worker.dataflow(|scope| func(scope)))
 }
 
+/// Executes a single-threaded timely dataflow computation with a seeded scheduling order.
+///
+/// The `example_deterministic` method is like `example`, but its worker schedules the operators
+/// active in each step in an order drawn from `seed`, rather than in the order they were
+/// constructed. Its `Thread` allocator delivers each channel's messages in the order they were
+/// sent, so a run is determined by its seed: a test that fails for some seed fails again for that
+/// seed, and varying the seed exercises different scheduling orders.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+/// use timely::dataflow::operators::{ToStream, Inspect, Delay, Accumulate, Capture};
+/// use timely::dataflow::operators::capture::Extract;
+///
+/// // the order in which two operators see records, and the output of a computation.
+/// let run = |seed| {
+///     let (order, counts) = timely::example_deterministic(seed, |scope| {
+///         let order = Arc::new(Mutex::new(Vec::new()));
+///         let (order_a, order_b) = (order.clone(), order.clone());
+///         let stream = (0 .. 10u64).to_stream(scope).delay(|x, _time| *x);
+///         stream.inspect(move |_| order_a.lock().unwrap().push('a'));
+///         stream.inspect(move |_| order_b.lock().unwrap().push('b'));
+///         (order, stream.count().capture())
+///     });
+///     let order = order.lock().unwrap().clone();
+///     (order, counts.extract())
+/// };
+///
+/// // a seed determines the order in which operators are scheduled.
+/// assert!((0 .. 5).all(|seed| run(seed) == run(seed)));
+/// assert!((1 .. 10).any(|seed| run(seed).0 != run(0).0));
+/// // the output of the computation does not depend on it.
+/// assert!((1 .. 10).all(|seed| run(seed).1 == run(0).1));
+/// ```
+pub fn example_deterministic<T, F>(seed: u64, func: F) -> T
+where
+    T: Send+'static,
+    F: FnOnce(&mut Child<Worker<crate::communication::allocator::thread::Thread>,u64>)->T+Send+Sync+'static
+{
+    crate::execute::execute_directly(move |worker| {
+        worker.activations().borrow_mut().set_seed(seed);
+        worker.dataflow(|scope| func(scope))
+    })
+}
+
 
 /// Executes a single-threaded timely dataflow computation.
 ///
@@ -145,6 +192,25 @@ where
 /// // the extracted data should have data (0..10) thrice at timestamp 0.
 /// assert_eq!(recv.extract()[0].1, (0..30).map(|x| x / 3).collect::<Vec<_>>());
 /// ```
+///
+/// The values returned by each worker can be recovered by joining the guards, in the order of
+/// the workers' indices.
+///
+/// ```rust
+/// use timely::dataflow::operators::{ToStream, Inspect};
+///
+/// let guards = timely::execute(timely::Configuration::Process(3), |worker| {
+///     let index = worker.index();
+///     worker.dataflow::<(),_,_>(|scope| {
+///         (0..10).to_stream(scope)
+///                .inspect(|x| println!("seen: {:?}", x));
+///     });
+///     index * 10
+/// }).unwrap();
+///
+/// let results = guards.join().into_iter().map(|result| result.unwrap()).collect::<Vec<_>>();
+/// assert_eq!(results, vec![0, 10, 20]);
+/// ```
 pub fn execute<T, F>(mut config: Configuration, func: F) -> Result<WorkerGuards<T>,String>
 where
     T:Send+'static,
@@ -59,15 +59,24 @@
 
 #[macro_use]
 extern crate abomonation_derive;
-extern crate abomonation;
+// public so that `newtype_timestamp!` can name it from other crates.
+#[doc(hidden)]
+pub extern crate abomonation;
+extern crate bincode;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate timely_communication;
 extern crate timely_bytes;
 extern crate timely_logging;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "futures")]
+extern crate futures;
+#[cfg(feature = "chrono")]
+extern crate chrono;
 
-pub use execute::{execute, execute_directly, execute_from_args, example};
+pub use execute::{execute, execute_directly, execute_from_args, example, example_deterministic};
 pub use order::PartialOrder;
 
 pub use timely_communication::Configuration;
@@ -1,4 +1,43 @@
 //! Traits, implementations, and macros related to logging timely events.
+//!
+//! Each worker records `TimelyEvent`s, describing the construction of operators and channels,
+//! the scheduling of operators, the messages sent and received, and progress updates, to the
+//! logger registered under the name `"timely"`. Events are timestamped with the elapsed time
+//! since the worker started, and are delivered in batches. A `BatchLogger` writes batches as a
+//! captured stream, which can be written to a file or a socket and replayed into a dataflow.
+//!
+//! # Examples
+//! ```
+//! use std::rc::Rc;
+//! use std::cell::RefCell;
+//! use timely::logging::TimelyEvent;
+//! use timely::dataflow::operators::{ToStream, Inspect};
+//!
+//! timely::execute_directly(|worker| {
+//!
+//!     // collect the names of operators as they are constructed.
+//!     let names = Rc::new(RefCell::new(Vec::new()));
+//!     let names2 = names.clone();
+//!     worker.log_register().insert::<TimelyEvent,_>("timely", move |_time, data| {
+//!         for (_time, _worker, event) in data.drain(..) {
+//!             if let TimelyEvent::Operates(event) = event {
+//!                 names2.borrow_mut().push(event.name);
+//!             }
+//!         }
+//!     });
+//!
+//!     worker.dataflow::<u64,_,_>(|scope| {
+//!         (0 .. 10).to_stream(scope)
+//!                  .inspect(|_| ());
+//!     });
+//!     while worker.step() { }
+//!
+//!     // dropping the logger flushes any remaining events.
+//!     worker.log_register().remove("timely");
+//!     assert!(names.borrow().contains(&"ToStream".to_string()));
+//!     assert!(names.borrow().contains(&"InspectBatch".to_string()));
+//! });
+//! ```
 
 /// Type alias for logging timely events.
 pub type WorkerIdentifier = usize;
@@ -7,8 +46,14 @@ pub type Logger<Event> = crate::logging_core::Logger<Event, WorkerIdentifier>;
 /// Logger for timely dataflow system events.
 pub type TimelyLogger = Logger<TimelyEvent>;
 
+use std::rc::Rc;
 use std::time::Duration;
-use crate::dataflow::operators::capture::{Event, EventPusher};
+use std::collections::{BTreeMap, HashMap};
+use crate::communication::Allocate;
+use crate::worker::Worker;
+use crate::dataflow::Stream;
+use crate::dataflow::scopes::Child;
+use crate::dataflow::operators::capture::{Event, EventPusher, EventLink, Replay};
 
 /// Logs events as a timely stream, with progress statements.
 pub struct BatchLogger<T, E, P> where P: EventPusher<Duration, (Duration, E, T)> {
@@ -46,6 +91,355 @@ impl<T, E, P> Drop for BatchLogger<T, E, P> where P: EventPusher<Duration, (Dura
     }
 }
 
+/// Constructs a dataflow from the timely events the worker logs, timestamped by the time at which
+/// each batch of events was logged.
+///
+/// The dataflow is constructed by `func` from a stream of the worker's events, after which a
+/// logger for the stream is registered under `"timely"`, replacing any existing logger. Events
+/// are logged by the dataflows the worker constructs afterwards, but not by the monitoring
+/// dataflow itself. The stream is complete once the logger is removed and each dataflow logging
+/// to it has completed.
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+/// use std::cell::RefCell;
+/// use timely::logging::TimelyEvent;
+/// use timely::dataflow::operators::{ToStream, Map, Inspect};
+///
+/// timely::execute_directly(|worker| {
+///
+///     // a dataflow which monitors the operators constructed by later dataflows.
+///     let names = Rc::new(RefCell::new(Vec::new()));
+///     let names2 = names.clone();
+///     timely::logging::monitor(worker, |events| {
+///         events.flat_map(|(_time, _worker, event)| match event {
+///                   TimelyEvent::Operates(event) => Some(event.name),
+///                   _ => None,
+///               })
+///               .inspect(move |name| names2.borrow_mut().push(name.clone()));
+///     });
+///
+///     worker.dataflow::<u64,_,_>(|scope| {
+///         (0 .. 10).to_stream(scope)
+///                  .inspect(|_| ());
+///     });
+///
+///     // removing the logger lets the monitoring dataflow complete.
+///     worker.log_register().remove("timely");
+///     while worker.step() { }
+///
+///     assert!(names.borrow().contains(&"ToStream".to_string()));
+///     assert!(names.borrow().contains(&"InspectBatch".to_string()));
+/// });
+/// ```
+pub fn monitor<A, R, F>(worker: &mut Worker<A>, func: F) -> R
+where
+    A: Allocate,
+    F: FnOnce(&Stream<Child<Worker<A>, Duration>, (Duration, WorkerIdentifier, TimelyEvent)>)->R,
+{
+    let link = Rc::new(EventLink::new());
+    let replay = Some(link.clone());
+    let result = worker.dataflow(move |scope| func(&replay.replay_into(scope)));
+    let mut logger = BatchLogger::new(link);
+    worker.log_register().insert::<TimelyEvent,_>("timely", move |time, data| logger.publish_batch(time, data));
+    result
+}
+
+/// Collects the operators and channels of dataflows from logged events, to render as a DOT graph.
+///
+/// Operators are labeled with their names, and the operators of each nested scope are drawn in a
+/// cluster together with a point representing the scope's boundary, at which channels enter and
+/// leave the scope. Channels are labeled with their output and input port numbers.
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+/// use std::cell::RefCell;
+/// use timely::logging::{TimelyEvent, DotGraph};
+/// use timely::dataflow::Scope;
+/// use timely::dataflow::operators::{ToStream, Map, Inspect, Enter, Leave};
+///
+/// timely::execute_directly(|worker| {
+///
+///     let graph = Rc::new(RefCell::new(DotGraph::new()));
+///     let graph2 = graph.clone();
+///     worker.log_register().insert::<TimelyEvent,_>("timely", move |_time, data| {
+///         for (_time, _worker, event) in data.iter() {
+///             graph2.borrow_mut().observe(event);
+///         }
+///     });
+///
+///     worker.dataflow::<u64,_,_>(|scope| {
+///         let stream = (0 .. 10).to_stream(scope);
+///         scope.region(|inner| stream.enter(inner).map(|x| x + 1).leave())
+///              .inspect(|_| ());
+///     });
+///     worker.log_register().remove("timely");
+///
+///     let dot = graph.borrow().to_dot();
+///     assert!(dot.starts_with("digraph dataflow {"));
+///     assert!(dot.contains("[label=\"ToStream\"]"));
+///     assert!(dot.contains("subgraph cluster_"));
+///     assert!(dot.contains("[label=\"Map\"]"));
+/// });
+/// ```
+#[derive(Default)]
+pub struct DotGraph {
+    operators: BTreeMap<Vec<usize>, String>,
+    channels: Vec<ChannelsEvent>,
+}
+
+impl DotGraph {
+    /// Allocates a new, empty graph.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records the operator or channel described by `event`, ignoring other events.
+    pub fn observe(&mut self, event: &TimelyEvent) {
+        match event {
+            TimelyEvent::Operates(event) => { self.operators.insert(event.addr.clone(), event.name.clone()); },
+            TimelyEvent::Channels(event) => { self.channels.push(event.clone()); },
+            _ => { },
+        }
+    }
+
+    /// Renders the recorded operators and channels in the DOT language.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dataflow {\n");
+        for addr in self.operators.keys() {
+            // render each operator not contained in a recorded scope, along with its contents.
+            if !(1 .. addr.len()).any(|len| self.operators.contains_key(&addr[.. len])) {
+                self.render_operator(addr, 1, &mut dot);
+            }
+        }
+        for channel in self.channels.iter() {
+            dot.push_str(&format!(
+                "  {} -> {} [taillabel=\"{}\", headlabel=\"{}\"];\n",
+                node_name(&channel.scope_addr, channel.source.0),
+                node_name(&channel.scope_addr, channel.target.0),
+                channel.source.1,
+                channel.target.1,
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders an operator, as a cluster of its contents if it is a scope.
+    fn render_operator(&self, addr: &[usize], depth: usize, dot: &mut String) {
+        let indent = "  ".repeat(depth);
+        let name = &self.operators[addr];
+        let node = node_name(addr, 0);
+        let children = self.operators.keys().filter(|x| x.len() == addr.len() + 1 && x.starts_with(addr)).collect::<Vec<_>>();
+        if children.is_empty() {
+            dot.push_str(&format!("{}{} [label={:?}];\n", indent, node, name));
+        }
+        else {
+            dot.push_str(&format!("{}subgraph cluster_{} {{\n", indent, node));
+            dot.push_str(&format!("{}  label={:?};\n", indent, name));
+            dot.push_str(&format!("{}  {} [label=\"\", shape=point];\n", indent, node));
+            for child in children {
+                self.render_operator(child, depth + 1, dot);
+            }
+            dot.push_str(&format!("{}}}\n", indent));
+        }
+    }
+}
+
+/// The DOT node for operator `index` of the scope at `scope_addr`, where index zero is the scope.
+fn node_name(scope_addr: &[usize], index: usize) -> String {
+    let mut name = String::from("op");
+    for step in scope_addr.iter() {
+        name.push_str(&format!("_{}", step));
+    }
+    if index > 0 {
+        name.push_str(&format!("_{}", index));
+    }
+    name
+}
+
+/// Counts how often each operator is scheduled and the time it spends scheduled, from logged events.
+///
+/// The time spent by an operator that contains a scope, such as a dataflow or a region, includes
+/// the time spent by the operators within the scope.
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+/// use std::cell::RefCell;
+/// use timely::logging::{TimelyEvent, ScheduleMetrics};
+/// use timely::dataflow::operators::{ToStream, Map, Inspect};
+///
+/// timely::execute_directly(|worker| {
+///
+///     let metrics = Rc::new(RefCell::new(ScheduleMetrics::new()));
+///     let metrics2 = metrics.clone();
+///     worker.log_register().insert::<TimelyEvent,_>("timely", move |_time, data| {
+///         for (time, _worker, event) in data.iter() {
+///             metrics2.borrow_mut().observe(time, event);
+///         }
+///     });
+///
+///     worker.dataflow::<u64,_,_>(|scope| {
+///         (0 .. 10).to_stream(scope)
+///                  .map(|x| x + 1)
+///                  .inspect(|_| ());
+///     });
+///     while worker.step() { }
+///     worker.log_register().remove("timely");
+///
+///     // report the operators that spent the most time scheduled first.
+///     let mut snapshot = metrics.borrow().snapshot();
+///     snapshot.sort_by(|x, y| y.elapsed.cmp(&x.elapsed));
+///     let map = snapshot.iter().find(|x| x.name == "Map").unwrap();
+///     assert!(map.schedules > 0);
+/// });
+/// ```
+#[derive(Default)]
+pub struct ScheduleMetrics {
+    started: HashMap<usize, Duration>,
+    metrics: BTreeMap<usize, OperatorMetrics>,
+}
+
+/// The scheduling metrics of one operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorMetrics {
+    /// Worker-unique identifier for the operator.
+    pub id: usize,
+    /// Sequence of nested scope identifiers indicating the path from the root to this instance.
+    pub addr: Vec<usize>,
+    /// A helpful name.
+    pub name: String,
+    /// The number of times the operator has been scheduled.
+    pub schedules: usize,
+    /// The total time the operator has spent scheduled.
+    pub elapsed: Duration,
+}
+
+impl ScheduleMetrics {
+    /// Allocates a new collection of metrics, with no operators.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records the operator construction or scheduling described by `event`, logged at `time`.
+    pub fn observe(&mut self, time: &Duration, event: &TimelyEvent) {
+        match event {
+            TimelyEvent::Operates(event) => {
+                self.metrics.insert(event.id, OperatorMetrics {
+                    id: event.id,
+                    addr: event.addr.clone(),
+                    name: event.name.clone(),
+                    schedules: 0,
+                    elapsed: Duration::default(),
+                });
+            },
+            TimelyEvent::Schedule(event) => {
+                match event.start_stop {
+                    StartStop::Start => { self.started.insert(event.id, *time); },
+                    StartStop::Stop => {
+                        let start = self.started.remove(&event.id);
+                        if let (Some(start), Some(metrics)) = (start, self.metrics.get_mut(&event.id)) {
+                            metrics.schedules += 1;
+                            metrics.elapsed += *time - start;
+                        }
+                    },
+                }
+            },
+            _ => { },
+        }
+    }
+
+    /// The current metrics of each operator, ordered by operator identifier.
+    pub fn snapshot(&self) -> Vec<OperatorMetrics> {
+        self.metrics.values().cloned().collect()
+    }
+}
+
+/// Counts the messages and records sent and received on each channel, from logged events.
+///
+/// Counts are local to the worker whose events are observed; comparing the records received
+/// on a channel at each worker reveals skew in how data are exchanged.
+///
+/// # Examples
+/// ```
+/// use std::rc::Rc;
+/// use std::cell::RefCell;
+/// use timely::logging::{TimelyEvent, ChannelMetrics};
+/// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+///
+/// timely::execute_directly(|worker| {
+///
+///     let metrics = Rc::new(RefCell::new(ChannelMetrics::new()));
+///     let metrics2 = metrics.clone();
+///     worker.log_register().insert::<TimelyEvent,_>("timely", move |_time, data| {
+///         for (_time, _worker, event) in data.iter() {
+///             metrics2.borrow_mut().observe(event);
+///         }
+///     });
+///
+///     worker.dataflow::<u64,_,_>(|scope| {
+///         (0 .. 10).to_stream(scope)
+///                  .exchange(|x| *x)
+///                  .inspect(|_| ());
+///     });
+///     while worker.step() { }
+///     worker.log_register().remove("timely");
+///
+///     let snapshot = metrics.borrow().snapshot();
+///     assert!(snapshot.iter().all(|x| x.records_sent == x.records_received));
+///     assert!(snapshot.iter().any(|x| x.records_received == 10));
+/// });
+/// ```
+#[derive(Default)]
+pub struct ChannelMetrics {
+    metrics: BTreeMap<usize, ChannelVolume>,
+}
+
+/// The volume of data sent and received on one channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelVolume {
+    /// Worker-unique identifier for the channel.
+    pub channel: usize,
+    /// The number of messages sent.
+    pub messages_sent: usize,
+    /// The number of records sent.
+    pub records_sent: usize,
+    /// The number of messages received.
+    pub messages_received: usize,
+    /// The number of records received.
+    pub records_received: usize,
+}
+
+impl ChannelMetrics {
+    /// Allocates a new collection of metrics, with no channels.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records the message send or receive described by `event`, ignoring other events.
+    pub fn observe(&mut self, event: &TimelyEvent) {
+        if let TimelyEvent::Messages(event) = event {
+            let volume = self.metrics.entry(event.channel).or_insert_with(|| ChannelVolume { channel: event.channel, ..Default::default() });
+            if event.is_send {
+                volume.messages_sent += 1;
+                volume.records_sent += event.length;
+            }
+            else {
+                volume.messages_received += 1;
+                volume.records_received += event.length;
+            }
+        }
+    }
+
+    /// The current volume of each channel, ordered by channel identifier.
+    pub fn snapshot(&self) -> Vec<ChannelVolume> {
+        self.metrics.values().cloned().collect()
+    }
+}
+
 #[derive(Serialize, Deserialize, Abomonation, Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 /// The creation of an `Operate` implementor.
 pub struct OperatesEvent {
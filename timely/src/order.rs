@@ -47,6 +47,14 @@ macro_rules! implement_total {
 implement_partial!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, (), ::std::time::Duration,);
 implement_total!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, (), ::std::time::Duration,);
 
+/// Pairs are ordered lexicographically, first by their first elements and then by their second.
+impl<T1: TotalOrder, T2: TotalOrder> PartialOrder for (T1, T2) {
+    #[inline] fn less_equal(&self, other: &Self) -> bool {
+        self.0.less_than(&other.0) || (self.0 == other.0 && self.1.less_equal(&other.1))
+    }
+}
+impl<T1: TotalOrder, T2: TotalOrder> TotalOrder for (T1, T2) { }
+
 
 use std::fmt::{Formatter, Error, Debug};
 
@@ -288,13 +288,31 @@ where
         //
         // We should be able to schedule arbitrary subsets of children, as
         // long as we eventually schedule all children that need to do work.
-        let mut previous = 0;
-        while let Some(Reverse(index)) = self.temp_active.pop() {
-            // De-duplicate, and don't revisit.
-            if index > previous {
-                // TODO: This is a moment where a scheduling decision happens.
-                self.activate_child(index);
-                previous = index;
+        if self.activations.borrow().is_seeded() {
+            // Schedule each active child once, in rounds ordered by the seeded generator.
+            let mut scheduled = vec![false; self.children.len()];
+            let mut round = Vec::new();
+            while !self.temp_active.is_empty() {
+                round.extend(self.temp_active.drain().map(|Reverse(index)| index));
+                round.retain(|&index| index > 0 && !scheduled[index]);
+                round.sort();
+                round.dedup();
+                self.activations.borrow_mut().permute(&mut round[..]);
+                for index in round.drain(..) {
+                    scheduled[index] = true;
+                    self.activate_child(index);
+                }
+            }
+        }
+        else {
+            let mut previous = 0;
+            while let Some(Reverse(index)) = self.temp_active.pop() {
+                // De-duplicate, and don't revisit.
+                if index > previous {
+                    // TODO: This is a moment where a scheduling decision happens.
+                    self.activate_child(index);
+                    previous = index;
+                }
             }
         }
 
@@ -567,6 +585,9 @@ struct PerOperatorState<T: Timestamp> {
     internal_summary: Vec<Vec<Antichain<T::Summary>>>,   // cached result from get_internal_summary.
 
     logging: Option<Logger>,
+
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,    // span entered for each invocation of the operator.
 }
 
 impl<T: Timestamp> PerOperatorState<T> {
@@ -588,6 +609,9 @@ impl<T: Timestamp> PerOperatorState<T> {
 
             shared_progress: Rc::new(RefCell::new(SharedProgress::new(inputs,outputs))),
             internal_summary: Vec::new(),
+
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::none(),
         }
     }
 
@@ -599,6 +623,12 @@ impl<T: Timestamp> PerOperatorState<T> {
         logging: Option<Logger>
     ) -> PerOperatorState<T>
     {
+        #[cfg(feature = "tracing")]
+        let span = {
+            _path.push(index);
+            tracing::trace_span!("operator", name = scope.name(), id = identifier, addr = ?_path)
+        };
+
         let local = scope.local();
         let inputs = scope.inputs();
         let outputs = scope.outputs();
@@ -624,6 +654,9 @@ impl<T: Timestamp> PerOperatorState<T> {
 
             shared_progress,
             internal_summary,
+
+            #[cfg(feature = "tracing")]
+            span,
         }
     }
 
@@ -644,7 +677,11 @@ impl<T: Timestamp> PerOperatorState<T> {
                 l.log(crate::logging::ScheduleEvent::start(self.id));
             }
 
-            let incomplete = operator.schedule();
+            let incomplete = {
+                #[cfg(feature = "tracing")]
+                let _guard = self.span.enter();
+                operator.schedule()
+            };
 
             // Perhaps log information about the stop of the schedule call.
             if let Some(l) = self.logging.as_mut() {
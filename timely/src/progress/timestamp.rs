@@ -6,9 +6,81 @@ use std::default::Default;
 use std::hash::Hash;
 
 use crate::communication::Data;
-use crate::order::PartialOrder;
+use crate::order::{PartialOrder, TotalOrder};
 
 /// A composite trait for types that serve as timestamps in timely dataflow.
+///
+/// Timestamps need only be partially ordered, by `PartialOrder`; their `Ord` implementation is
+/// used only to sort and deduplicate them, and need not agree with the partial order beyond
+/// extending it. Progress tracking, frontiers, and notifications all use the partial order.
+///
+/// # Examples
+///
+/// A timestamp of two independent counters, each advanced by its own loop.
+///
+/// ```
+/// #[macro_use] extern crate abomonation_derive;
+/// #[macro_use] extern crate serde_derive;
+///
+/// use timely::order::PartialOrder;
+/// use timely::progress::{Timestamp, PathSummary};
+/// use timely::progress::timestamp::Refines;
+/// use timely::dataflow::operators::{ToStream, Feedback, ConnectLoop, Concat, Capture};
+/// use timely::dataflow::operators::capture::Extract;
+///
+/// #[derive(Abomonation, Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// struct Pair { a: u64, b: u64 }
+///
+/// impl PartialOrder for Pair {
+///     fn less_equal(&self, other: &Self) -> bool {
+///         self.a <= other.a && self.b <= other.b
+///     }
+/// }
+///
+/// impl Timestamp for Pair {
+///     type Summary = Pair;
+///     fn minimum() -> Self { Pair::default() }
+/// }
+///
+/// impl PathSummary<Pair> for Pair {
+///     fn results_in(&self, src: &Pair) -> Option<Pair> {
+///         Some(Pair { a: src.a.checked_add(self.a)?, b: src.b.checked_add(self.b)? })
+///     }
+///     fn followed_by(&self, other: &Pair) -> Option<Pair> {
+///         self.results_in(other)
+///     }
+/// }
+///
+/// // allows a dataflow to use the timestamp.
+/// impl Refines<()> for Pair {
+///     fn to_inner(_: ()) -> Pair { Pair::default() }
+///     fn to_outer(self) { }
+///     fn summarize(_: Pair) { }
+/// }
+///
+/// fn main() {
+///     let captured = timely::execute_directly(|worker| {
+///         worker.dataflow::<Pair,_,_>(|scope| {
+///             // loops advancing each counter, for times up to (1, 1).
+///             let limit = Pair { a: 1, b: 1 };
+///             let (handle_a, cycle_a) = scope.feedback_bounded(Pair { a: 1, b: 0 }, limit);
+///             let (handle_b, cycle_b) = scope.feedback_bounded(Pair { a: 0, b: 1 }, limit);
+///             let stream = vec!["x"].to_stream(scope).concat(&cycle_a).concat(&cycle_b);
+///             stream.connect_loop(handle_a);
+///             stream.connect_loop(handle_b);
+///             stream.capture()
+///         })
+///     });
+///
+///     // the record reaches each time once by each path to it.
+///     assert_eq!(captured.extract(), vec![
+///         (Pair { a: 0, b: 0 }, vec!["x"]),
+///         (Pair { a: 0, b: 1 }, vec!["x"]),
+///         (Pair { a: 1, b: 0 }, vec!["x"]),
+///         (Pair { a: 1, b: 1 }, vec!["x", "x"]),
+///     ]);
+/// }
+/// ```
 pub trait Timestamp: Clone+Eq+PartialOrder+Debug+Send+Any+Data+Hash+Ord {
     /// A type summarizing action on a timestamp along a dataflow path.
     type Summary : PathSummary<Self> + 'static;
@@ -98,6 +170,202 @@ impl PathSummary<::std::time::Duration> for ::std::time::Duration {
     fn followed_by(&self, other: &::std::time::Duration) -> Option<::std::time::Duration> { self.checked_add(*other) }
 }
 
+/// Implements `Timestamp` and its supporting traits for newtypes wrapping an integer.
+///
+/// Each named type must be a tuple struct whose single field is an unsigned integer, and must
+/// derive `Clone`, `Copy`, `Debug`, `Default`, `PartialEq`, `Eq`, `PartialOrd`, `Ord`, and
+/// `Hash`, as well as `Serialize` and `Deserialize` if the `bincode` feature is enabled. The
+/// macro implements `Abomonation`, `PartialOrder`, `TotalOrder`, `Timestamp`, and `Refines<()>`,
+/// with the type serving as its own path summary by addition.
+///
+/// # Examples
+/// ```
+/// #[macro_use] extern crate timely;
+/// #[macro_use] extern crate serde_derive;
+///
+/// use timely::dataflow::operators::{Input, Inspect};
+///
+/// #[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// struct TxnId(u64);
+///
+/// newtype_timestamp!(TxnId);
+///
+/// fn main() {
+///     timely::execute_directly(|worker| {
+///         let mut input = worker.dataflow::<TxnId,_,_>(|scope| {
+///             let (input, stream) = scope.new_input();
+///             stream.inspect_time(|time, x: &u64| assert_eq!(time.0, *x));
+///             input
+///         });
+///         for txn in 0 .. 3 {
+///             input.send(txn);
+///             input.advance_to(TxnId(txn + 1));
+///             worker.step();
+///         }
+///     });
+/// }
+/// ```
+#[macro_export]
+macro_rules! newtype_timestamp {
+    ($($name:ident),* $(,)*) => (
+        $(
+            // the integer owns no memory, so its bytes are its whole encoding.
+            impl $crate::abomonation::Abomonation for $name { }
+            impl $crate::order::PartialOrder for $name {
+                #[inline] fn less_equal(&self, other: &Self) -> bool { self.0 <= other.0 }
+            }
+            impl $crate::order::TotalOrder for $name { }
+            impl $crate::progress::Timestamp for $name {
+                type Summary = $name;
+                fn minimum() -> Self { $name(0) }
+            }
+            impl $crate::progress::PathSummary<$name> for $name {
+                #[inline]
+                fn results_in(&self, src: &$name) -> Option<$name> { self.0.checked_add(src.0).map($name) }
+                #[inline]
+                fn followed_by(&self, other: &$name) -> Option<$name> { self.0.checked_add(other.0).map($name) }
+            }
+            impl $crate::progress::timestamp::Refines<()> for $name {
+                fn to_inner(_: ()) -> $name { $name(0) }
+                fn to_outer(self) { }
+                fn summarize(_: $name) { }
+            }
+        )*
+    )
+}
+
+/// Pairs of totally ordered timestamps, such as an epoch and a round within it, are ordered
+/// lexicographically. Path summaries advance each element by its own summary, which is correct
+/// for summaries that strictly advance the first element whenever they change it, as additions do.
+/// The elements are independent: the second does not carry into the first, so pairs do not model
+/// quantities with a bounded second element, such as seconds and nanoseconds.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::{Input, Inspect};
+/// use timely::progress::PathSummary;
+///
+/// timely::execute_directly(|worker| {
+///     let mut input = worker.dataflow::<(u64, u32),_,_>(|scope| {
+///         let (input, stream) = scope.new_input();
+///         stream.inspect_time(|time, x: &u64| assert_eq!(time.0, *x));
+///         input
+///     });
+///     for epoch in 0 .. 3 {
+///         input.send(epoch);
+///         input.advance_to((epoch, 1));
+///         input.advance_to((epoch + 1, 0));
+///         worker.step();
+///     }
+///
+///     assert_eq!((1, 2).results_in(&(3, 4)), Some((4, 6)));
+/// });
+/// ```
+impl<T1, T2> Timestamp for (T1, T2)
+where
+    T1: Timestamp+TotalOrder,
+    T2: Timestamp+TotalOrder,
+    T1::Summary: TotalOrder,
+    T2::Summary: TotalOrder,
+{
+    type Summary = (T1::Summary, T2::Summary);
+    fn minimum() -> Self { (T1::minimum(), T2::minimum()) }
+}
+impl<T1, T2> PathSummary<(T1, T2)> for (T1::Summary, T2::Summary)
+where
+    T1: Timestamp+TotalOrder,
+    T2: Timestamp+TotalOrder,
+    T1::Summary: TotalOrder,
+    T2::Summary: TotalOrder,
+{
+    #[inline]
+    fn results_in(&self, src: &(T1, T2)) -> Option<(T1, T2)> {
+        Some((self.0.results_in(&src.0)?, self.1.results_in(&src.1)?))
+    }
+    #[inline]
+    fn followed_by(&self, other: &Self) -> Option<Self> {
+        Some((self.0.followed_by(&other.0)?, self.1.followed_by(&other.1)?))
+    }
+}
+
+#[cfg(feature = "chrono")]
+pub use self::date_time::DateTime;
+
+#[cfg(feature = "chrono")]
+mod date_time {
+
+    use std::time::Duration;
+
+    use abomonation::Abomonation;
+    use chrono::{NaiveDateTime, TimeDelta};
+
+    use crate::order::{PartialOrder, TotalOrder};
+    use crate::progress::{Timestamp, PathSummary};
+    use super::Refines;
+
+    /// A chrono `NaiveDateTime` as a timestamp.
+    ///
+    /// Date-times are totally ordered, start at `NaiveDateTime::MIN`, and are advanced by
+    /// non-negative `Duration`s, which are their path summaries.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use chrono::{NaiveDate, Timelike};
+    /// use timely::dataflow::operators::{Input, Inspect};
+    /// use timely::progress::PathSummary;
+    /// use timely::progress::timestamp::DateTime;
+    ///
+    /// let midnight = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    /// let hour = Duration::from_secs(3600);
+    ///
+    /// timely::execute_directly(move |worker| {
+    ///     let mut input = worker.dataflow::<DateTime,_,_>(|scope| {
+    ///         let (input, stream) = scope.new_input();
+    ///         stream.inspect_time(|time, x: &u32| assert_eq!(time.0.hour(), *x));
+    ///         input
+    ///     });
+    ///     let mut time = DateTime(midnight);
+    ///     for hour_of_day in 0 .. 3 {
+    ///         input.advance_to(time);
+    ///         input.send(hour_of_day);
+    ///         time = hour.results_in(&time).unwrap();
+    ///         worker.step();
+    ///     }
+    /// });
+    /// ```
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct DateTime(pub NaiveDateTime);
+
+    // a `NaiveDateTime` owns no memory, so its bytes are its whole encoding.
+    impl Abomonation for DateTime { }
+
+    impl PartialOrder for DateTime {
+        #[inline] fn less_equal(&self, other: &Self) -> bool { self.0 <= other.0 }
+    }
+    impl TotalOrder for DateTime { }
+
+    impl Timestamp for DateTime {
+        type Summary = Duration;
+        fn minimum() -> Self { DateTime(NaiveDateTime::MIN) }
+    }
+
+    impl PathSummary<DateTime> for Duration {
+        #[inline]
+        fn results_in(&self, src: &DateTime) -> Option<DateTime> {
+            src.0.checked_add_signed(TimeDelta::from_std(*self).ok()?).map(DateTime)
+        }
+        #[inline]
+        fn followed_by(&self, other: &Duration) -> Option<Duration> { self.checked_add(*other) }
+    }
+
+    impl Refines<()> for DateTime {
+        fn to_inner(_: ()) -> DateTime { DateTime::minimum() }
+        fn to_outer(self) { }
+        fn summarize(_: Duration) { }
+    }
+}
+
 pub use self::refines::Refines;
 mod refines {
 
@@ -148,4 +416,13 @@ mod refines {
     }
 
     implement_refines_empty!(usize, u128, u64, u32, u16, u8, isize, i128, i64, i32, i16, i8, ::std::time::Duration,);
+
+    impl<T1, T2> Refines<()> for (T1, T2)
+    where
+        (T1, T2): Timestamp,
+    {
+        fn to_inner(_: ()) -> (T1, T2) { <(T1, T2)>::minimum() }
+        fn to_outer(self) { }
+        fn summarize(_: <(T1, T2) as Timestamp>::Summary) { }
+    }
 }
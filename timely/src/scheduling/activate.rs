@@ -8,6 +8,8 @@ use std::collections::BinaryHeap;
 use std::time::{Duration, Instant};
 use std::cmp::Reverse;
 
+use crate::dataflow::channels::partitioner::mix;
+
 /// Methods required to act as a timely scheduler.
 ///
 /// The core methods are the activation of "paths", sequences of integers, and
@@ -35,6 +37,30 @@ impl Scheduler for Box<dyn Scheduler> {
 }
 
 /// Allocation-free activation tracker.
+///
+/// Operators are only scheduled when their path has been activated, which happens when
+/// a channel delivers data to them, when their input frontiers change, or when they
+/// explicitly request re-activation through an `Activator`. Activations accumulate until
+/// `advance` is called, at which point they are sorted, deduplicated, and presented to
+/// `for_extensions`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Instant;
+/// use timely::scheduling::activate::Activations;
+///
+/// let mut activations = Activations::new(Instant::now());
+/// activations.activate(&[0, 3]);
+/// activations.activate(&[0, 1, 2]);
+/// activations.activate(&[0, 3]);
+/// activations.advance();
+///
+/// // extensions are reported in order, and without duplicates.
+/// let mut active = Vec::new();
+/// activations.for_extensions(&[0], |index| active.push(index));
+/// assert_eq!(active, vec![1, 3]);
+/// ```
 pub struct Activations {
     clean: usize,
     /// `(offset, length)`
@@ -49,6 +75,9 @@ pub struct Activations {
     // Delayed activations.
     timer: Instant,
     queue: BinaryHeap<Reverse<(Duration, Vec<usize>)>>,
+
+    // State of the generator that orders scheduling, if seeded.
+    order: Option<u64>,
 }
 
 impl Activations {
@@ -65,6 +94,51 @@ impl Activations {
             rx,
             timer,
             queue: BinaryHeap::new(),
+            order: None,
+        }
+    }
+
+    /// Schedules active tasks in an order drawn from `seed`, rather than in order of their paths.
+    ///
+    /// Workers and subgraphs consult `permute` before scheduling the tasks active in a step. Runs
+    /// with the same seed, whose tasks are activated alike, schedule their tasks in the same order,
+    /// and different seeds explore different orders.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Instant;
+    /// use timely::scheduling::activate::Activations;
+    ///
+    /// let order = |seed| {
+    ///     let mut activations = Activations::new(Instant::now());
+    ///     activations.set_seed(seed);
+    ///     let mut tasks = (0 .. 10).collect::<Vec<_>>();
+    ///     activations.permute(&mut tasks);
+    ///     tasks
+    /// };
+    ///
+    /// assert_eq!(order(7), order(7));
+    /// assert_ne!(order(7), order(8));
+    /// ```
+    pub fn set_seed(&mut self, seed: u64) {
+        self.order = Some(seed);
+    }
+
+    /// Indicates whether scheduling follows a seeded order.
+    pub fn is_seeded(&self) -> bool {
+        self.order.is_some()
+    }
+
+    /// Shuffles `tasks` with the seeded generator, and otherwise leaves them in place.
+    pub fn permute(&mut self, tasks: &mut [usize]) {
+        if let Some(state) = self.order.as_mut() {
+            // Fisher-Yates, drawing from splitmix64.
+            for index in (1 .. tasks.len()).rev() {
+                *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                let other = mix(*state) % (index as u64 + 1);
+                tasks.swap(index, other as usize);
+            }
         }
     }
 
@@ -184,6 +258,7 @@ impl Activations {
 }
 
 /// A thread-safe handle to an `Activations`.
+#[derive(Clone)]
 pub struct SyncActivations {
     tx: Sender<Vec<usize>>,
     thread: Thread,
@@ -214,6 +289,46 @@ impl SyncActivations {
 }
 
 /// A capability to activate a specific path.
+///
+/// An operator can use an activator for its own address to request that it be
+/// scheduled again, for example when it deliberately yields with work outstanding.
+///
+/// # Examples
+///
+/// ```
+/// use timely::dataflow::operators::{ToStream, Capture};
+/// use timely::dataflow::operators::capture::Extract;
+/// use timely::dataflow::operators::generic::Operator;
+/// use timely::dataflow::channels::pact::Pipeline;
+/// use timely::scheduling::Scheduler;
+///
+/// let extracted = timely::example(|scope| {
+///     let scope2 = scope.clone();
+///     (0..1000u64)
+///         .to_stream(scope)
+///         .unary(Pipeline, "Yielding", move |_cap, info| {
+///             let activator = scope2.activator_for(&info.address[..]);
+///             let mut stash = Vec::new();
+///             move |input, output| {
+///                 input.for_each(|time, data| {
+///                     stash.push((time.retain(), data.replace(Vec::new())));
+///                 });
+///                 // produce at most 100 records per invocation.
+///                 if let Some((cap, mut data)) = stash.pop() {
+///                     let split = if data.len() > 100 { data.len() - 100 } else { 0 };
+///                     output.session(&cap).give_iterator(data.drain(split ..));
+///                     if !data.is_empty() { stash.push((cap, data)); }
+///                 }
+///                 // ask to be rescheduled if work remains.
+///                 if !stash.is_empty() { activator.activate(); }
+///             }
+///         })
+///         .capture()
+/// });
+///
+/// assert_eq!(extracted.extract()[0].1.len(), 1000);
+/// ```
+#[derive(Clone)]
 pub struct Activator {
     path: Vec<usize>,
     queue: Rc<RefCell<Activations>>,
@@ -248,6 +363,7 @@ impl Activator {
 }
 
 /// A thread-safe version of `Activator`.
+#[derive(Clone)]
 pub struct SyncActivator {
     path: Vec<usize>,
     queue: SyncActivations,
@@ -4,8 +4,10 @@ use std::rc::Rc;
 use std::cell::RefCell;
 
 pub mod activate;
+pub mod timers;
 
 pub use self::activate::{Activations, Activator, ActivateOnDrop, SyncActivator};
+pub use self::timers::Timers;
 
 /// A type that can be scheduled.
 pub trait Schedule {
@@ -0,0 +1,87 @@
+//! Wall-clock timers for operators.
+
+use std::time::{Duration, Instant};
+
+use crate::scheduling::Activator;
+
+/// Timers that schedule an operator when they expire, each identified by a key.
+///
+/// An operator constructs its timers from an activator for its own address, schedules them from
+/// its logic, and drains the expired timers each time it is scheduled. Each scheduled timer
+/// causes the operator to be scheduled once it expires, whether or not records arrive, as a
+/// basis for timeouts, cache expiry, or heartbeats. A key may hold a capability, for operators
+/// that produce output when a timer expires.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, Instant};
+/// use timely::scheduling::{Scheduler, Timers};
+/// use timely::dataflow::channels::pact::Pipeline;
+/// use timely::dataflow::operators::{ToStream, Capture};
+/// use timely::dataflow::operators::generic::operator::Operator;
+/// use timely::dataflow::operators::capture::Extract;
+///
+/// let start = Instant::now();
+/// let captured = timely::example(|scope| {
+///     let stream = (0..3u64).to_stream(scope);
+///     stream.unary(Pipeline, "Postpone", |_capability, info| {
+///         let mut timers = Timers::new(stream.scope().activator_for(&info.address[..]));
+///         move |input, output| {
+///             // produce each record ten milliseconds after it arrives.
+///             input.for_each(|time, data| {
+///                 let capability = time.retain();
+///                 for x in data.iter() {
+///                     timers.schedule(Duration::from_millis(10), (capability.clone(), *x));
+///                 }
+///             });
+///             while let Some((capability, x)) = timers.next_expired() {
+///                 output.session(&capability).give(x);
+///             }
+///         }
+///     })
+///     .capture()
+/// });
+///
+/// assert_eq!(captured.extract(), vec![(0, vec![0, 1, 2])]);
+/// assert!(start.elapsed() >= Duration::from_millis(10));
+/// ```
+pub struct Timers<K> {
+    activator: Activator,
+    // timers by their expiry, in order of expiry.
+    timers: Vec<(Instant, K)>,
+}
+
+impl<K> Timers<K> {
+    /// Allocates timers that schedule the operator of `activator`.
+    pub fn new(activator: Activator) -> Self {
+        Timers { activator, timers: Vec::new() }
+    }
+
+    /// Schedules a timer identified by `key` to expire after `delay`.
+    pub fn schedule(&mut self, delay: Duration, key: K) {
+        let expiry = Instant::now() + delay;
+        let position = self.timers.iter().position(|(other, _)| *other > expiry).unwrap_or(self.timers.len());
+        self.timers.insert(position, (expiry, key));
+        self.activator.activate_after(delay);
+    }
+
+    /// Removes and returns the key of the earliest timer to have expired, if any.
+    pub fn next_expired(&mut self) -> Option<K> {
+        if self.timers.first().map(|(expiry, _)| *expiry <= Instant::now()) == Some(true) {
+            Some(self.timers.remove(0).1)
+        }
+        else {
+            None
+        }
+    }
+
+    /// The number of timers that have not been returned by `next_expired`.
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// True if every timer has been returned by `next_expired`.
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+}
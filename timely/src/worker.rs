@@ -25,6 +25,8 @@ pub trait AsWorker : Scheduler {
     fn index(&self) -> usize;
     /// Number of peer workers.
     fn peers(&self) -> usize;
+    /// Indices of the peer workers in the same process, including this worker.
+    fn local_peers(&self) -> ::std::ops::Range<usize> { 0 .. self.peers() }
     /// Allocates a new channel from a supplied identifier and address.
     ///
     /// The identifier is used to identify the underlying channel and route
@@ -73,6 +75,7 @@ pub struct Worker<A: Allocate> {
 impl<A: Allocate> AsWorker for Worker<A> {
     fn index(&self) -> usize { self.allocator.borrow().index() }
     fn peers(&self) -> usize { self.allocator.borrow().peers() }
+    fn local_peers(&self) -> ::std::ops::Range<usize> { self.allocator.borrow().local_peers() }
     fn allocate<D: Data>(&mut self, identifier: usize, address: &[usize]) -> (Vec<Box<dyn Push<Message<D>>>>, Box<dyn Pull<Message<D>>>) {
         if address.len() == 0 { panic!("Unacceptable address: Length zero"); }
         let mut paths = self.paths.borrow_mut();
@@ -102,6 +105,36 @@ impl<A: Allocate> Scheduler for Worker<A> {
 
 impl<A: Allocate> Worker<A> {
     /// Allocates a new `Worker` bound to a channel allocator.
+    ///
+    /// # Examples
+    ///
+    /// A worker can be bound to a `Sim` allocator, which delays the delivery of messages, to
+    /// check that a computation's results do not depend on the timing of its channels.
+    ///
+    /// ```
+    /// use std::rc::Rc;
+    /// use std::cell::RefCell;
+    /// use timely::communication::allocator::{Thread, Sim};
+    /// use timely::dataflow::operators::{ToStream, Exchange, Delay, Accumulate, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let run = |seed| {
+    ///     let mut worker = timely::worker::Worker::new(Sim::new(Thread::new(), seed, 0.5));
+    ///     let events = Rc::new(RefCell::new(Vec::new()));
+    ///     worker.dataflow::<u64,_,_>(|scope| {
+    ///         (0 .. 100u64).to_stream(scope)
+    ///                      .exchange(|x| *x)
+    ///                      .delay(|x, _time| x % 5)
+    ///                      .count()
+    ///                      .capture_into(events.clone());
+    ///     });
+    ///     while worker.step() { }
+    ///     let extracted = events.borrow().clone().extract();
+    ///     extracted
+    /// };
+    ///
+    /// assert!((0 .. 10).all(|seed| run(seed) == run(seed + 1)));
+    /// ```
     pub fn new(c: A) -> Worker<A> {
         let now = Instant::now();
         let index = c.index();
@@ -154,6 +187,12 @@ impl<A: Allocate> Worker<A> {
     /// `None` allows the worker to park indefinitely, whereas a value of
     /// `Some(Duration::new(0, 0))` will return without parking the thread.
     ///
+    /// The worker only parks when no operator is scheduled to run. A parked
+    /// worker is unparked when another worker pushes data or progress updates
+    /// into one of its channels, when the network receives data for it, or when
+    /// a `SyncActivator` activates one of its operators from another thread.
+    /// Operators that have scheduled a delayed activation bound the duration.
+    ///
     /// # Examples
     ///
     /// ```
@@ -222,10 +261,16 @@ impl<A: Allocate> Worker<A> {
         }
         else {   // Schedule active dataflows.
 
+            #[cfg(feature = "tracing")]
+            let _guard = tracing::trace_span!("worker", index = self.index()).entered();
+
             let active_dataflows = &mut self.active_dataflows;
             self.activations
                 .borrow_mut()
                 .for_extensions(&[], |index| active_dataflows.push(index));
+            self.activations
+                .borrow_mut()
+                .permute(&mut active_dataflows[..]);
 
             let mut dataflows = self.dataflows.borrow_mut();
             for index in active_dataflows.drain(..) {
@@ -453,6 +498,35 @@ impl<A: Allocate> Worker<A> {
     /// Various other resources will be cleaned up, though the method is currently in
     /// public beta rather than expected to work. Please report all crashes and unmet
     /// expectations!
+    ///
+    /// Dataflows that complete, because all of their inputs are closed and all of their
+    /// operators have finished, are removed without a call to this method. It is needed only
+    /// for dataflows that should be torn down before they complete.
+    ///
+    /// # Examples
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::operators::{ToStream, Input, Inspect};
+    ///     use timely::dataflow::InputHandle;
+    ///
+    ///     // a dataflow which completes, and is removed once it does.
+    ///     let finite = worker.next_dataflow_index();
+    ///     worker.dataflow::<usize,_,_>(|scope| { (0 .. 10).to_stream(scope).inspect(|_| ()); });
+    ///
+    ///     // a dataflow whose open input prevents it from completing.
+    ///     let mut input = InputHandle::<usize, usize>::new();
+    ///     let unbounded = worker.next_dataflow_index();
+    ///     worker.dataflow(|scope| { scope.input_from(&mut input).inspect(|_| ()); });
+    ///
+    ///     assert_eq!(worker.installed_dataflows().len(), 2);
+    ///     while worker.installed_dataflows().contains(&finite) { worker.step(); }
+    ///     assert_eq!(worker.installed_dataflows(), vec![unbounded]);
+    ///
+    ///     worker.drop_dataflow(unbounded);
+    ///     assert!(worker.installed_dataflows().is_empty());
+    /// });
+    /// ```
     pub fn drop_dataflow(&mut self, dataflow_identifier: usize) {
         if let Some(mut entry) = self.dataflows.borrow_mut().remove(&dataflow_identifier) {
             // Garbage collect channel_id to path information.
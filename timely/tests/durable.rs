@@ -0,0 +1,73 @@
+extern crate timely;
+
+use std::cell::RefCell;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use timely::dataflow::operators::{Input, Inspect};
+use timely::dataflow::operators::input::{Handle, DurableHandle};
+
+fn log_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("timely-durable-{}-{}.log", std::process::id(), name))
+}
+
+/// Opens the log at `path`, sends `epochs` further records at successive epochs, and returns
+/// the records the dataflow receives, including those replayed.
+fn run(path: &Path, epochs: u64) -> Vec<(u64, u64)> {
+    let path = path.to_path_buf();
+    timely::execute_directly(move |worker| {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received2 = received.clone();
+        let mut input = Handle::<u64, u64>::new();
+        worker.dataflow(|scope| {
+            scope.input_from(&mut input)
+                 .inspect_time(move |time, x| received2.borrow_mut().push((*time, *x)));
+        });
+        let mut input = DurableHandle::open(input, &path).unwrap();
+        for _ in 0 .. epochs {
+            let time = *input.time();
+            input.send(time * 10);
+            input.advance_to(time + 1);
+        }
+        input.close().unwrap();
+        while worker.step() { }
+        received.replace(Vec::new())
+    })
+}
+
+#[test]
+fn truncated_log_replays_intact_entries() {
+    let path = log_path("truncated");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(run(&path, 3), vec![(0, 0), (1, 10), (2, 20)]);
+
+    // cut the last entry, the advance to epoch 3, short.
+    let length = std::fs::metadata(&path).unwrap().len();
+    OpenOptions::new().write(true).open(&path).unwrap().set_len(length - 3).unwrap();
+
+    // the input resumes at epoch 2, and the partial entry is replaced.
+    assert_eq!(run(&path, 1), vec![(0, 0), (1, 10), (2, 20), (2, 20)]);
+    assert_eq!(run(&path, 0), vec![(0, 0), (1, 10), (2, 20), (2, 20)]);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn corrupted_log_replays_entries_before_corruption() {
+    let path = log_path("corrupted");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(run(&path, 3), vec![(0, 0), (1, 10), (2, 20)]);
+
+    // flip a byte within the entry for epoch 1.
+    let mut bytes = std::fs::read(&path).unwrap();
+    let middle = bytes.len() / 2;
+    bytes[middle] ^= 0xFF;
+    std::fs::write(&path, &bytes).unwrap();
+
+    // entries from the corrupted one on fail their checksums and are discarded.
+    assert_eq!(run(&path, 0), vec![(0, 0)]);
+    assert!(std::fs::metadata(&path).unwrap().len() < middle as u64);
+
+    std::fs::remove_file(&path).unwrap();
+}
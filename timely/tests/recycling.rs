@@ -0,0 +1,66 @@
+extern crate timely;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use timely::Configuration;
+use timely::dataflow::InputHandle;
+use timely::dataflow::operators::{Input, Map, Exchange, Probe};
+
+// Counts the allocations of the test process.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// Counts the allocations made while `rounds` batches pass through a chain of `stages` operators,
+// which exchange records between workers if `exchange` is set.
+fn allocations(config: Configuration, stages: usize, exchange: bool, rounds: u64) -> usize {
+    let allocations = timely::execute(config, move |worker| {
+        let mut input = InputHandle::new();
+        let probe = worker.dataflow(|scope| {
+            let mut stream = scope.input_from(&mut input);
+            for _ in 0 .. stages {
+                stream = if exchange { stream.exchange(|x| *x) } else { stream.map_in_place(|x| *x += 1) };
+            }
+            stream.probe()
+        });
+        let mut count = |round: u64| {
+            input.send(round);
+            input.advance_to(round + 1);
+            worker.step_while(|| probe.less_than(input.time()));
+        };
+        // warm up channels and other state before counting.
+        for round in 0 .. 10 { count(round); }
+        let before = ALLOCATIONS.load(Ordering::SeqCst);
+        for round in 10 .. 10 + rounds { count(round); }
+        ALLOCATIONS.load(Ordering::SeqCst) - before
+    }).unwrap().join().remove(0).unwrap();
+    allocations
+}
+
+#[test]
+fn thread_channels_recycle_messages() {
+    // each round sends one message along each of the stages' channels.
+    let allocations = allocations(Configuration::Thread, 10, false, 1000);
+    assert!(allocations < 10 * 1000);
+}
+
+#[test]
+fn process_channels_recycle_messages() {
+    // each round sends one message between the workers at each of the stages.
+    let allocations = allocations(Configuration::Process(2), 10, true, 1000);
+    assert!(allocations < 10 * 1000);
+}
@@ -0,0 +1,129 @@
+extern crate timely;
+
+use std::sync::{Arc, Mutex};
+
+use timely::Configuration;
+use timely::communication::allocator::Generic;
+use timely::worker::Worker;
+use timely::dataflow::Stream;
+use timely::dataflow::scopes::Child;
+use timely::dataflow::channels::pact::{ParallelizationContract, KeyHash, KeyedPipeline};
+use timely::dataflow::channels::pact::{Balance, LocalBalance, Partitioned, RangeExchange};
+use timely::dataflow::channels::partitioner::Range;
+use timely::dataflow::operators::{ToStream, Exchange, Inspect, Map};
+use timely::dataflow::operators::generic::operator::Operator;
+
+type Scope<'a> = Child<'a, Worker<Generic>, u64>;
+
+// Introduces `0 .. records` spread across `workers` workers, applies `route`, and returns each
+// resulting record with the index of the worker that holds it.
+fn routed<F>(workers: usize, records: u64, route: F) -> Vec<(usize, u64)>
+where
+    F: for<'a> Fn(&Stream<Scope<'a>, u64>)->Stream<Scope<'a>, u64>+Send+Sync+'static,
+{
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let results2 = results.clone();
+    timely::execute(Configuration::Process(workers), move |worker| {
+        let (index, peers) = (worker.index(), worker.peers());
+        let results = results2.clone();
+        worker.dataflow::<u64,_,_>(|scope| {
+            let stream = (0 .. records).filter(move |x| *x as usize % peers == index).to_stream(scope);
+            route(&stream).inspect(move |x| results.lock().unwrap().push((index, *x)));
+        });
+    }).unwrap();
+    let mut results = results.lock().unwrap().clone();
+    results.sort();
+    results
+}
+
+// Forwards records through an operator whose input uses `pact`.
+fn forward<'a, P: ParallelizationContract<u64, u64>>(stream: &Stream<Scope<'a>, u64>, pact: P) -> Stream<Scope<'a>, u64> {
+    let mut vector = Vec::new();
+    stream.unary(pact, "Forward", move |_, _| move |input, output| {
+        input.for_each(|time, data| {
+            data.swap(&mut vector);
+            output.session(&time).give_vec(&mut vector);
+        });
+    })
+}
+
+struct Tens;
+impl KeyHash<u64> for Tens {
+    fn hash(x: &u64) -> u64 { x / 10 }
+}
+
+// Counts the records held by each of `workers` workers.
+fn counts(results: &[(usize, u64)], workers: usize) -> Vec<usize> {
+    (0 .. workers).map(|worker| results.iter().filter(|&&(index, _)| index == worker).count()).collect()
+}
+
+#[test]
+fn balance_spreads_equal_keys() {
+    // every record has the same key, but the work is still shared.
+    let results = routed(3, 30, |stream| forward(&stream.map(|_| 7), Balance));
+    assert_eq!(counts(&results, 3), vec![10, 10, 10]);
+}
+
+#[test]
+fn local_balance_spreads_within_process() {
+    let results = routed(3, 30, |stream| forward(stream, LocalBalance));
+    assert_eq!(counts(&results, 3), vec![10, 10, 10]);
+}
+
+#[test]
+fn partitioned_routes_by_partitioner() {
+    let results = routed(2, 6, |stream| forward(stream, Partitioned::new(Range::new(vec![3], |x: &u64| *x))));
+    assert_eq!(results, vec![(0, 0), (0, 1), (0, 2), (1, 3), (1, 4), (1, 5)]);
+}
+
+#[test]
+fn range_exchange_splits_sample() {
+    let results = routed(3, 1000, |stream| {
+        // each worker chooses the same boundaries from the same sample.
+        let sample = (0 .. 1000u64).step_by(10).collect();
+        forward(stream, RangeExchange::from_sample(sample, 3, |x: &u64| *x))
+    });
+    assert_eq!(results.len(), 1000);
+    // the sample is split at 330 and 660.
+    assert!(results.iter().all(|&(index, x)| index == if x < 330 { 0 } else if x < 660 { 1 } else { 2 }));
+}
+
+#[test]
+fn exchange_by_routes_by_key() {
+    for workers in 1 .. 4 {
+        let results = routed(workers, 100, |stream| stream.exchange_by::<Tens>());
+        assert_eq!(results.len(), 100);
+        assert!(results.iter().all(|&(index, x)| index == (x / 10) as usize % workers));
+    }
+}
+
+#[test]
+fn keyed_pipeline_leaves_partitioned_records() {
+    let results = routed(3, 100, |stream| {
+        let keyed = stream.exchange_by::<Tens>();
+        assert!(KeyedPipeline::<Tens, _>::new(&keyed).is_pipeline());
+        forward(&keyed, KeyedPipeline::<Tens, _>::new(&keyed))
+    });
+    assert_eq!(results.len(), 100);
+    assert!(results.iter().all(|&(index, x)| index == (x / 10) as usize % 3));
+}
+
+#[test]
+fn keyed_pipeline_exchanges_unpartitioned_records() {
+    let results = routed(3, 100, |stream| {
+        assert!(!KeyedPipeline::<Tens, _>::new(stream).is_pipeline());
+        forward(stream, KeyedPipeline::<Tens, _>::new(stream))
+    });
+    assert_eq!(results.len(), 100);
+    assert!(results.iter().all(|&(index, x)| index == (x / 10) as usize % 3));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic]
+fn keyed_pipeline_checks_partitioning() {
+    routed(2, 100, |stream| {
+        let marked = stream.clone().with_partitioning::<Tens>();
+        forward(&marked, KeyedPipeline::<Tens, _>::new(&marked))
+    });
+}